@@ -1,5 +1,6 @@
 use crate::game::state::GameState;
-use crate::game::board::get_property;
+use crate::game::debt::Creditor;
+use crate::ws::messages::ServerMessage;
 
 pub fn handle_buy_property(game: &mut GameState, player_id: &str) -> Result<(), String> {
     // 0. Check state
@@ -9,7 +10,7 @@ pub fn handle_buy_property(game: &mut GameState, player_id: &str) -> Result<(),
     );
     
     game.check_turn(player_id)?;
-    game.check_phase(crate::game::state::GamePhase::EndTurn)?;
+    game.check_offer_phase()?;
 
     // 1. Find player
     let player_idx = game.players.iter().position(|p| p.id == player_id)
@@ -19,7 +20,7 @@ pub fn handle_buy_property(game: &mut GameState, player_id: &str) -> Result<(),
     let position = game.players[player_idx].position;
     
     // 3. Check if it's a valid property
-    let property_info = get_property(position).ok_or("Not a property")?;
+    let property_info = game.board.property(position).cloned().ok_or("Not a property")?;
     
     // 4. Check if already owned
     let property_state = game.properties.iter_mut().find(|p| p.id == position)
@@ -54,15 +55,9 @@ pub fn handle_buy_property(game: &mut GameState, player_id: &str) -> Result<(),
     Ok(())
 }
 
-/// Result of a pay rent attempt
-pub enum PayRentResult {
-    /// Rent paid successfully
-    Success,
-    /// Player cannot pay - bankruptcy required. Contains creditor_id and rent amount.
-    BankruptcyRequired { creditor_id: String, rent_owed: i32 },
-}
-
-pub fn handle_pay_rent(game: &mut GameState, player_id: &str) -> Result<PayRentResult, String> {
+/// Pays the rent for the current tile. Rent the player cannot cover becomes a
+/// debt to the owner, settled during DebtResolution.
+pub fn handle_pay_rent(game: &mut GameState, player_id: &str) -> Result<Vec<ServerMessage>, String> {
     // 0. Check state
     tracing::info!(
         "[FSM] handle_pay_rent: START - player_id={}, current_turn={}, phase={:?}",
@@ -78,12 +73,15 @@ pub fn handle_pay_rent(game: &mut GameState, player_id: &str) -> Result<PayRentR
     let player_pos = game.players[player_idx].position;
     
     // 2. Get property info and state
-    let _property_info = get_property(player_pos).ok_or("Not a property")?;
+    game.board.property(player_pos).ok_or("Not a property")?;
     let property_state = game.properties.iter().find(|p| p.id == player_pos)
         .ok_or("Property state not found")?;
         
     // 3. Check ownership
     let owner_id = property_state.owner_id.as_ref().ok_or("Property not owned")?;
+    if game.rent_paid {
+        return Err("Rent already paid".to_string());
+    }
     if owner_id == player_id {
         tracing::warn!(
             "[FSM] handle_pay_rent: FAIL - player owns this property, position={}",
@@ -99,32 +97,16 @@ pub fn handle_pay_rent(game: &mut GameState, player_id: &str) -> Result<PayRentR
         0 // Should not happen if moved by dice, but if moved by card without roll, might be 0.
           // For utilities, this means 0 rent if no dice roll recorded.
     };
-    let rent = game.calculate_rent(player_pos, dice_sum);
+    let rent = game.rent_due(player_pos, dice_sum);
     
     tracing::info!(
         "[FSM] handle_pay_rent: CALCULATING - position={}, dice_sum={}, rent={}, owner={}",
         player_pos, dice_sum, rent, owner_id
     );
     
-    // 5. Check funds - if insufficient, return bankruptcy required
-    if game.players[player_idx].money < rent {
-        tracing::warn!(
-            "[FSM] handle_pay_rent: BANKRUPTCY REQUIRED - player_id={}, money={}, rent={}, creditor={}",
-            player_id, game.players[player_idx].money, rent, owner_id
-        );
-        return Ok(PayRentResult::BankruptcyRequired { 
-            creditor_id: owner_id.clone(), 
-            rent_owed: rent 
-        });
-    }
-    
-    // 6. Transfer money
-    game.players[player_idx].money -= rent;
-    
-    if let Some(owner_idx) = game.players.iter().position(|p| &p.id == owner_id) {
-        game.players[owner_idx].money += rent;
-    }
-    
+    // 5. Transfer money, or record the debt if the player is short
+    let owner_id = owner_id.clone();
+    let events = game.charge(player_idx, rent, Creditor::Player(owner_id.clone()));
     game.rent_paid = true;
     
     tracing::info!(
@@ -132,7 +114,7 @@ pub fn handle_pay_rent(game: &mut GameState, player_id: &str) -> Result<PayRentR
         player_id, player_pos, rent, owner_id
     );
 
-    Ok(PayRentResult::Success)
+    Ok(events)
 }
 
 #[cfg(test)]
@@ -143,18 +125,8 @@ mod tests {
     #[test]
     fn test_buy_property_success() {
         let mut game = GameState::new();
-        // Add a player
-        game.players.push(PlayerState {
-            id: "player1".to_string(),
-            name: "Test Player".to_string(),
-            money: 100_000,
-            position: 1, // ID 1 is Matematika (Price 60_000)
-            color: "red".to_string(),
-            is_in_jail: false,
-            jail_turns: 0,
-            doubles_count: 0,
-            held_cards: Vec::new(),
-        });
+        // Add a player on ID 1, Matematika (Price 60_000)
+        game.players.push(PlayerState { position: 1, ..PlayerState::new("player1", "Test Player", 100_000) });
         game.phase = crate::game::state::GamePhase::EndTurn;
 
         // Ensure property is not owned
@@ -176,17 +148,8 @@ mod tests {
     #[test]
     fn test_buy_property_insufficient_funds() {
         let mut game = GameState::new();
-        game.players.push(PlayerState {
-            id: "player1".to_string(),
-            name: "Test Player".to_string(),
-            money: 10_000, // Not enough for 60k
-            position: 1,
-            color: "red".to_string(),
-            is_in_jail: false,
-            jail_turns: 0,
-            doubles_count: 0,
-            held_cards: Vec::new(),
-        });
+        // Not enough for 60k
+        game.players.push(PlayerState { position: 1, ..PlayerState::new("player1", "Test Player", 10_000) });
         game.phase = crate::game::state::GamePhase::EndTurn;
 
         let result = handle_buy_property(&mut game, "player1");
@@ -196,28 +159,8 @@ mod tests {
     #[test]
     fn test_buy_property_already_owned() {
         let mut game = GameState::new();
-        game.players.push(PlayerState {
-            id: "player1".to_string(),
-            name: "Player 1".to_string(),
-            money: 100_000,
-            position: 1,
-            color: "red".to_string(),
-            is_in_jail: false,
-            jail_turns: 0,
-            doubles_count: 0,
-            held_cards: Vec::new(),
-        });
-        game.players.push(PlayerState {
-            id: "player2".to_string(),
-            name: "Player 2".to_string(),
-            money: 100_000,
-            position: 1,
-            color: "blue".to_string(),
-            is_in_jail: false,
-            jail_turns: 0,
-            doubles_count: 0,
-            held_cards: Vec::new(),
-        });
+        game.players.push(PlayerState { position: 1, ..PlayerState::new("player1", "Player 1", 100_000) });
+        game.players.push(PlayerState { position: 1, ..PlayerState::new("player2", "Player 2", 100_000) });
         game.phase = crate::game::state::GamePhase::EndTurn;
 
         // Player 1 buys
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use crate::game::state::GamePhase;
+
+/// Seconds an auction stays open after the last bid
+pub const AUCTION_DURATION_SECS: u64 = 10;
+/// Minimum opening bid and minimum raise over the current highest bid
+pub const MIN_BID_INCREMENT: i32 = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Auction {
+    pub id: String,
+    pub property_id: usize,
+    pub highest_bid: i32,
+    pub highest_bidder: Option<String>,
+    pub bidders: Vec<String>, // Players who have not passed yet
+    pub ends_at: u64,         // Unix timestamp in seconds
+    pub return_phase: GamePhase, // Phase to resume once the auction is over
+}
+
+impl Auction {
+    pub fn min_next_bid(&self) -> i32 {
+        if self.highest_bidder.is_some() {
+            self.highest_bid + MIN_BID_INCREMENT
+        } else {
+            MIN_BID_INCREMENT
+        }
+    }
+
+    /// The auction is decided once nobody is left or only the highest bidder remains
+    pub fn is_decided(&self) -> bool {
+        match self.bidders.as_slice() {
+            [] => true,
+            [only] => self.highest_bidder.as_ref() == Some(only),
+            _ => false,
+        }
+    }
+}
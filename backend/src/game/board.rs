@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Board shipped with the binary, used when no board files are found on disk
+const DEFAULT_BOARD_JSON: &str = include_str!("../../boards/keliling_ui.json");
+pub const DEFAULT_BOARD_ID: &str = "keliling_ui";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PropertyGroup {
@@ -14,65 +20,176 @@ pub enum PropertyGroup {
     Utility,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PropertyInfo {
-    pub id: usize,
-    pub name: &'static str,
-    pub price: i32,
-    pub rent: i32, // Base rent for now
     pub group: PropertyGroup,
+    pub price: i32,
+    /// Rent schedule: base, 1-4 houses, hotel.
+    /// Railroads use the first four entries (1-4 railroads owned),
+    /// utilities the first two as dice multipliers (1-2 utilities owned).
+    pub rent: [i32; 6],
+    #[serde(default)]
+    pub house_cost: i32,
+    pub mortgage_value: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum TileKind {
+    Go,
+    Property(PropertyInfo),
+    CommunityChest,
+    Chance,
+    Tax { amount: i32 },
+    Jail,
+    FreeParking,
+    GoToJail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Tile {
+    pub id: usize,
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: TileKind,
+}
+
+/// A board edition: every tile in play order, starting with GO at position 0
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Board {
+    pub id: String,
+    pub name: String,
+    /// Id of the card deck set played on this board
+    #[serde(default = "default_deck_set_id")]
+    pub deck_set_id: String,
+    pub tiles: Vec<Tile>,
+}
+
+fn default_deck_set_id() -> String {
+    crate::game::cards::DEFAULT_DECK_SET_ID.to_string()
 }
 
-pub const PROPERTIES: &[PropertyInfo] = &[
-    // Brown
-    PropertyInfo { id: 1, name: "Matematika", price: 60_000, rent: 2_000, group: PropertyGroup::Brown },
-    PropertyInfo { id: 3, name: "Fisika", price: 60_000, rent: 4_000, group: PropertyGroup::Brown },
-    
-    // Railroads
-    PropertyInfo { id: 5, name: "Stasiun UI", price: 200_000, rent: 25_000, group: PropertyGroup::Railroad },
-    PropertyInfo { id: 15, name: "Bikun", price: 200_000, rent: 25_000, group: PropertyGroup::Railroad },
-    PropertyInfo { id: 25, name: "Gerbang Utama", price: 200_000, rent: 25_000, group: PropertyGroup::Railroad },
-    PropertyInfo { id: 35, name: "Balairung", price: 200_000, rent: 25_000, group: PropertyGroup::Railroad },
-
-    // Light Blue
-    PropertyInfo { id: 6, name: "Sastra Inggris", price: 100_000, rent: 6_000, group: PropertyGroup::LightBlue },
-    PropertyInfo { id: 8, name: "Arkeologi", price: 100_000, rent: 6_000, group: PropertyGroup::LightBlue },
-    PropertyInfo { id: 9, name: "Filsafat", price: 120_000, rent: 8_000, group: PropertyGroup::LightBlue },
-
-    // Pink
-    PropertyInfo { id: 11, name: "Ilmu Komunikasi", price: 140_000, rent: 10_000, group: PropertyGroup::Pink },
-    PropertyInfo { id: 13, name: "Hubungan Internasional", price: 140_000, rent: 10_000, group: PropertyGroup::Pink },
-    PropertyInfo { id: 14, name: "Sosiologi", price: 160_000, rent: 12_000, group: PropertyGroup::Pink },
-
-    // Utilities
-    PropertyInfo { id: 12, name: "Perpustakaan UI", price: 150_000, rent: 0, group: PropertyGroup::Utility }, // Special rent logic
-    PropertyInfo { id: 28, name: "Danau UI", price: 150_000, rent: 0, group: PropertyGroup::Utility },
-
-    // Orange
-    PropertyInfo { id: 16, name: "Hukum Perdata", price: 180_000, rent: 14_000, group: PropertyGroup::Orange },
-    PropertyInfo { id: 18, name: "Hukum Pidana", price: 180_000, rent: 14_000, group: PropertyGroup::Orange },
-    PropertyInfo { id: 19, name: "Hukum Tata Negara", price: 200_000, rent: 16_000, group: PropertyGroup::Orange },
-
-    // Red
-    PropertyInfo { id: 21, name: "Akuntansi", price: 220_000, rent: 18_000, group: PropertyGroup::Red },
-    PropertyInfo { id: 23, name: "Manajemen", price: 220_000, rent: 18_000, group: PropertyGroup::Red },
-    PropertyInfo { id: 24, name: "Ilmu Ekonomi", price: 240_000, rent: 20_000, group: PropertyGroup::Red },
-
-    // Yellow
-    PropertyInfo { id: 26, name: "Ilmu Komputer", price: 260_000, rent: 22_000, group: PropertyGroup::Yellow },
-    PropertyInfo { id: 27, name: "Sistem Informasi", price: 260_000, rent: 22_000, group: PropertyGroup::Yellow },
-    PropertyInfo { id: 29, name: "Teknologi Informasi", price: 280_000, rent: 24_000, group: PropertyGroup::Yellow },
-
-    // Green
-    PropertyInfo { id: 31, name: "Teknik Sipil", price: 300_000, rent: 26_000, group: PropertyGroup::Green },
-    PropertyInfo { id: 32, name: "Teknik Elektro", price: 300_000, rent: 26_000, group: PropertyGroup::Green },
-    PropertyInfo { id: 34, name: "Teknik Mesin", price: 320_000, rent: 28_000, group: PropertyGroup::Green },
-
-    // Dark Blue
-    PropertyInfo { id: 37, name: "Kedokteran Gigi", price: 350_000, rent: 35_000, group: PropertyGroup::DarkBlue },
-    PropertyInfo { id: 39, name: "Kedokteran", price: 400_000, rent: 50_000, group: PropertyGroup::DarkBlue },
-];
-
-pub fn get_property(id: usize) -> Option<&'static PropertyInfo> {
-    PROPERTIES.iter().find(|p| p.id == id)
+impl Default for Board {
+    fn default() -> Self {
+        Board::from_json(DEFAULT_BOARD_JSON).expect("built-in board definition is valid")
+    }
+}
+
+impl Board {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let board: Board = serde_json::from_str(json).map_err(|e| format!("Invalid board definition: {}", e))?;
+        board.validate()?;
+        Ok(board)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        Board::from_json(&json)
+    }
+
+    /// Loads every `*.json` board in `dir`, keyed by board id. Invalid files are
+    /// skipped with a warning; the built-in board is always available.
+    pub fn load_dir(dir: &Path) -> HashMap<String, Board> {
+        let mut boards = HashMap::new();
+        let default = Board::default();
+        boards.insert(default.id.clone(), default);
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Board directory {} not readable ({}), using built-in board", dir.display(), e);
+                return boards;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match Board::load(&path) {
+                Ok(board) => {
+                    tracing::info!("Loaded board '{}' from {}", board.id, path.display());
+                    boards.insert(board.id.clone(), board);
+                }
+                Err(e) => tracing::warn!("Skipping board {}: {}", path.display(), e),
+            }
+        }
+        boards
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.tiles.is_empty() {
+            return Err("Board has no tiles".to_string());
+        }
+        for (position, tile) in self.tiles.iter().enumerate() {
+            if tile.id != position {
+                return Err(format!("Tile '{}' has id {} but is at position {}", tile.name, tile.id, position));
+            }
+        }
+        if self.tiles[0].kind != TileKind::Go {
+            return Err("First tile must be GO".to_string());
+        }
+        if !self.tiles.iter().any(|t| t.kind == TileKind::Jail) {
+            return Err("Board has no jail tile".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn size(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn tile(&self, position: usize) -> Option<&Tile> {
+        self.tiles.get(position)
+    }
+
+    pub fn property(&self, id: usize) -> Option<&PropertyInfo> {
+        match self.tiles.get(id).map(|t| &t.kind) {
+            Some(TileKind::Property(info)) => Some(info),
+            _ => None,
+        }
+    }
+
+    pub fn properties(&self) -> impl Iterator<Item = (&Tile, &PropertyInfo)> {
+        self.tiles.iter().filter_map(|t| match &t.kind {
+            TileKind::Property(info) => Some((t, info)),
+            _ => None,
+        })
+    }
+
+    pub fn jail_position(&self) -> usize {
+        self.tiles.iter().position(|t| t.kind == TileKind::Jail).unwrap_or(0)
+    }
+
+    /// First tile of `group` strictly after `from`, wrapping around the board
+    pub fn next_in_group(&self, from: usize, group: &PropertyGroup) -> Option<usize> {
+        let size = self.size();
+        (1..=size)
+            .map(|offset| (from + offset) % size)
+            .find(|&pos| self.property(pos).is_some_and(|info| &info.group == group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_board_is_complete() {
+        let board = Board::default();
+        assert_eq!(board.size(), 40);
+        assert_eq!(board.properties().count(), 28);
+        assert_eq!(board.jail_position(), 10);
+        assert_eq!(board.next_in_group(36, &PropertyGroup::Railroad), Some(5));
+    }
+
+    #[test]
+    fn test_rejects_misnumbered_tiles() {
+        let json = r#"{"id": "bad", "name": "Bad", "tiles": [
+            {"id": 0, "name": "GO", "type": "Go"},
+            {"id": 2, "name": "Jail", "type": "Jail"}
+        ]}"#;
+        assert!(Board::from_json(json).is_err());
+    }
 }
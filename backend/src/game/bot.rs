@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use crate::game::board::PropertyGroup;
+use crate::game::command::GameCommand;
+use crate::game::state::{CardEffect, GamePhase, GameState, PlayerState};
+use crate::game::trade::{TradeOffer, TradeStatus};
+
+/// How a bot player weighs cash against property
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BotStrategy {
+    /// Keeps a large cash reserve, never bids over list price and only takes
+    /// trades clearly in its favour
+    Cautious,
+    /// Buys nearly everything, bids past list price, leaves jail early and
+    /// builds as soon as it can
+    Aggressive,
+}
+
+impl BotStrategy {
+    /// Cash the bot keeps back before buying, bidding or building
+    fn reserve(self, state: &GameState) -> i32 {
+        match self {
+            BotStrategy::Cautious => state.rules.starting_money / 5,
+            BotStrategy::Aggressive => state.rules.starting_money / 20,
+        }
+    }
+
+    /// Highest bid the bot makes for a property listed at `price`
+    fn max_bid(self, price: i32) -> i32 {
+        match self {
+            BotStrategy::Cautious => price,
+            BotStrategy::Aggressive => price * 3 / 2,
+        }
+    }
+
+    /// Percentage of what it gives away that a trade must bring in
+    fn trade_margin(self) -> i32 {
+        match self {
+            BotStrategy::Cautious => 130,
+            BotStrategy::Aggressive => 100,
+        }
+    }
+
+    /// Next command the bot sends, or `None` while the game is not waiting on it
+    pub fn next_command(self, state: &GameState, bot_id: &str) -> Option<GameCommand> {
+        let player = state.players.iter().find(|p| p.id == bot_id)?;
+
+        if let Some(command) = self.answer_trade(state, player) {
+            return Some(command);
+        }
+        if let Some(decision) = state.mortgage_decisions.iter().find(|d| d.player_id == player.id) {
+            let unmortgage = player.money - decision.unmortgage_cost >= self.reserve(state) * 2;
+            return Some(GameCommand::ResolveMortgage { property_id: decision.property_id, unmortgage });
+        }
+        if state.phase == GamePhase::Auction {
+            return self.bid(state, player);
+        }
+        if state.acting_player_id() != Some(bot_id) {
+            return None;
+        }
+
+        match state.phase {
+            // A tile reached with doubles is bought or declined before rolling again
+            GamePhase::Rolling if state.offer_pending() => Some(self.finish_turn(state, player)),
+            GamePhase::Rolling => Some(self.start_turn(state, player)),
+            GamePhase::EndTurn => Some(self.finish_turn(state, player)),
+            GamePhase::DebtResolution => Some(self.settle_debts(state, player)),
+            _ => None,
+        }
+    }
+
+    /// Leaves jail with a card, or with bail when aggressive, then rolls
+    fn start_turn(self, state: &GameState, player: &PlayerState) -> GameCommand {
+        if player.is_in_jail {
+            if player.held_cards.iter().any(|c| c.effect == CardEffect::GetOutOfJail) {
+                return GameCommand::UseJailCard;
+            }
+            if self == BotStrategy::Aggressive && player.money - state.rules.bail_amount >= self.reserve(state) {
+                return GameCommand::PayBail;
+            }
+        }
+        GameCommand::RollDice
+    }
+
+    /// Buys or declines the tile, pays rent, spends spare cash, then ends the turn
+    fn finish_turn(self, state: &GameState, player: &PlayerState) -> GameCommand {
+        let reserve = self.reserve(state);
+        let tile = state.properties.iter().find(|p| p.id == player.position);
+        if let (Some(tile), Some(info)) = (tile, state.board.property(player.position)) {
+            match &tile.owner_id {
+                None if !state.offer_declined => {
+                    return if player.money - info.price >= reserve {
+                        GameCommand::BuyProperty
+                    } else {
+                        GameCommand::DeclineProperty
+                    };
+                }
+                Some(owner_id) if owner_id != &player.id && !state.rent_paid => return GameCommand::PayRent,
+                _ => {}
+            }
+        }
+
+        if let Some(property_id) = self.property_to_build(state, player, reserve) {
+            return GameCommand::BuyBuilding { property_id };
+        }
+        if let Some(property_id) = property_to_unmortgage(state, player, reserve * 2) {
+            return GameCommand::UnmortgageProperty { property_id };
+        }
+        GameCommand::EndTurn
+    }
+
+    /// Pays off its debts, selling buildings and mortgaging property to raise
+    /// the money, and goes bankrupt once nothing is left
+    fn settle_debts(self, state: &GameState, player: &PlayerState) -> GameCommand {
+        let owed: i32 = state.debts.iter().filter(|d| d.debtor_id == player.id).map(|d| d.amount).sum();
+        if player.money >= owed {
+            return GameCommand::PayDebt;
+        }
+        if let Some(property_id) = building_to_sell(state, player) {
+            return GameCommand::SellBuilding { property_id };
+        }
+        if let Some(property_id) = property_to_mortgage(state, player) {
+            return GameCommand::MortgageProperty { property_id };
+        }
+        GameCommand::DeclareBankruptcy
+    }
+
+    fn bid(self, state: &GameState, player: &PlayerState) -> Option<GameCommand> {
+        let auction = state.auction.as_ref()?;
+        if !auction.bidders.contains(&player.id) || auction.highest_bidder.as_ref() == Some(&player.id) {
+            return None;
+        }
+        let price = state.board.property(auction.property_id).map(|info| info.price).unwrap_or(0);
+        let amount = auction.min_next_bid();
+        if amount <= self.max_bid(price) && player.money - amount >= self.reserve(state) {
+            Some(GameCommand::PlaceBid { amount })
+        } else {
+            Some(GameCommand::PassAuction)
+        }
+    }
+
+    /// Accepts the first pending trade offered to the bot that is worth it, and
+    /// rejects those that are not
+    fn answer_trade(self, state: &GameState, player: &PlayerState) -> Option<GameCommand> {
+        let mut trades: Vec<_> = state.active_trades.values()
+            .filter(|t| t.target_player_id == player.id && t.status == TradeStatus::Pending)
+            .collect();
+        trades.sort_by(|a, b| a.id.cmp(&b.id));
+        let trade = trades.first()?;
+
+        let gain = offer_value(state, &trade.offer);
+        let cost = offer_value(state, &trade.request);
+        let trade_id = trade.id.clone();
+        if player.money >= trade.request.money && gain * 100 >= cost * self.trade_margin() {
+            Some(GameCommand::AcceptTrade { trade_id })
+        } else {
+            Some(GameCommand::RejectTrade { trade_id })
+        }
+    }
+
+    /// Cheapest legal house or hotel the bot can afford above `reserve`
+    fn property_to_build(self, state: &GameState, player: &PlayerState, reserve: i32) -> Option<usize> {
+        state.properties.iter()
+            .filter(|p| p.owner_id.as_ref() == Some(&player.id) && p.houses < 5)
+            .filter_map(|p| state.board.property(p.id).map(|info| (p, info)))
+            .filter(|(_, info)| !matches!(info.group, PropertyGroup::Railroad | PropertyGroup::Utility))
+            .filter(|(_, info)| player.money - info.house_cost >= reserve)
+            .filter(|(p, info)| state.check_monopoly(&player.id, info.group.clone()) && group_houses(state, &info.group).min() == Some(p.houses))
+            .filter(|(p, _)| if p.houses == 4 { state.total_hotels > 0 } else { state.total_houses > 0 })
+            .min_by_key(|(p, info)| (info.house_cost, p.id))
+            .map(|(p, _)| p.id)
+    }
+}
+
+/// Whether a bot backs the running kick or mute vote: always against another
+/// bot, and against a human once most of the other humans voted yes
+pub fn backs_vote(target_is_bot: bool, humans_for: usize, humans: usize) -> bool {
+    target_is_bot || humans_for * 2 > humans
+}
+
+/// What a side of a trade is worth to the bot: cash, jail cards at the bail
+/// they save, plus list prices less what it would cost to lift any mortgage
+fn offer_value(state: &GameState, offer: &TradeOffer) -> i32 {
+    offer.money + offer.jail_cards as i32 * state.rules.bail_amount + offer.property_ids.iter()
+        .filter_map(|id| Some((state.properties.iter().find(|p| p.id == *id)?, state.board.property(*id)?)))
+        .map(|(p, info)| {
+            let mortgage = if p.is_mortgaged { info.mortgage_value + info.mortgage_value / 10 } else { 0 };
+            info.price + p.houses as i32 * info.house_cost - mortgage
+        })
+        .sum::<i32>()
+}
+
+fn group_houses<'a>(state: &'a GameState, group: &'a PropertyGroup) -> impl Iterator<Item = u8> + 'a {
+    state.properties.iter()
+        .filter(move |p| state.board.property(p.id).is_some_and(|info| &info.group == group))
+        .map(|p| p.houses)
+}
+
+/// A building the bot may sell while keeping its groups even
+fn building_to_sell(state: &GameState, player: &PlayerState) -> Option<usize> {
+    state.properties.iter()
+        .filter(|p| p.owner_id.as_ref() == Some(&player.id) && p.houses > 0)
+        .filter(|p| p.houses < 5 || state.total_houses >= 4)
+        .find(|p| {
+            state.board.property(p.id)
+                .is_some_and(|info| group_houses(state, &info.group).max() == Some(p.houses))
+        })
+        .map(|p| p.id)
+}
+
+/// The bot's first unimproved, unmortgaged property
+fn property_to_mortgage(state: &GameState, player: &PlayerState) -> Option<usize> {
+    state.properties.iter()
+        .find(|p| p.owner_id.as_ref() == Some(&player.id) && !p.is_mortgaged && p.houses == 0)
+        .map(|p| p.id)
+}
+
+/// A mortgaged property the bot can lift while keeping `reserve`
+fn property_to_unmortgage(state: &GameState, player: &PlayerState, reserve: i32) -> Option<usize> {
+    state.properties.iter()
+        .filter(|p| p.owner_id.as_ref() == Some(&player.id) && p.is_mortgaged)
+        .find(|p| {
+            state.board.property(p.id)
+                .is_some_and(|info| player.money - (info.mortgage_value + info.mortgage_value / 10) >= reserve)
+        })
+        .map(|p| p.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::rng::GameRng;
+
+    fn bot_game() -> GameState {
+        let mut game = GameState::new();
+        for id in ["bot", "human"] {
+            game.players.push(PlayerState::new(id, id, 1500000));
+        }
+        game.phase = GamePhase::Rolling;
+        game.rng = GameRng::new(1);
+        game
+    }
+
+    /// Lets the bot play until it has nothing left to do
+    fn play(game: &mut GameState, strategy: BotStrategy, steps: usize) {
+        for step in 0..steps {
+            let Some(command) = strategy.next_command(game, "bot") else {
+                return;
+            };
+            game.apply(Some("bot"), &command, 1000 + step as u64)
+                .unwrap_or_else(|e| panic!("bot sent {:?}: {}", command, e));
+        }
+    }
+
+    #[test]
+    fn test_bot_plays_a_full_turn() {
+        let mut game = bot_game();
+        game.rng.script_dice(&[(1, 2)]).unwrap(); // Lands on an unowned property
+
+        play(&mut game, BotStrategy::Aggressive, 10);
+        assert_eq!(game.properties.iter().find(|p| p.id == 3).unwrap().owner_id.as_deref(), Some("bot"));
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.phase, GamePhase::Rolling);
+    }
+
+    #[test]
+    fn test_strategies_differ_on_purchases() {
+        for (strategy, buys) in [(BotStrategy::Cautious, false), (BotStrategy::Aggressive, true)] {
+            let mut game = bot_game();
+            game.players[0].money = 250_000;
+            game.players[0].position = 1;
+            game.phase = GamePhase::EndTurn;
+            let command = strategy.next_command(&game, "bot").unwrap();
+            assert_eq!(matches!(command, GameCommand::BuyProperty), buys, "{:?}", strategy);
+        }
+    }
+
+    #[test]
+    fn test_bot_answers_trades_by_value() {
+        let mut game = bot_game();
+        game.properties.iter_mut().find(|p| p.id == 1).unwrap().owner_id = Some("bot".to_string());
+        let price = game.board.property(1).unwrap().price;
+        let sell = |money| TradeOffer { money, property_ids: Vec::new(), jail_cards: 0 };
+        let buy = TradeOffer { money: 0, property_ids: vec![1], jail_cards: 0 };
+
+        game.handle_propose_trade("human".to_string(), "bot".to_string(), sell(price / 2), buy.clone(), 1000).unwrap();
+        assert!(matches!(BotStrategy::Aggressive.next_command(&game, "bot"), Some(GameCommand::RejectTrade { .. })));
+        game.active_trades.clear();
+
+        game.handle_propose_trade("human".to_string(), "bot".to_string(), sell(price * 2), buy, 1000).unwrap();
+        assert!(matches!(BotStrategy::Cautious.next_command(&game, "bot"), Some(GameCommand::AcceptTrade { .. })));
+    }
+
+    #[test]
+    fn test_bot_mortgages_before_going_bankrupt() {
+        let mut game = bot_game();
+        game.properties.iter_mut().find(|p| p.id == 1).unwrap().owner_id = Some("bot".to_string());
+        game.players[0].money = 0;
+        game.charge(0, 20_000, crate::game::debt::Creditor::Bank);
+        assert_eq!(game.phase, GamePhase::DebtResolution);
+
+        play(&mut game, BotStrategy::Cautious, 2); // Mortgage, then pay
+        assert!(game.debts.is_empty());
+        assert!(game.properties.iter().find(|p| p.id == 1).unwrap().is_mortgaged);
+        assert!(game.players.iter().any(|p| p.id == "bot"));
+    }
+}
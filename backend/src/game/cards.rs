@@ -1,56 +1,83 @@
+use crate::game::rng::GameRng;
 use crate::game::state::Card;
 use rand::seq::SliceRandom;
-use rand::rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 
-pub fn initialize_decks() -> (Vec<Card>, Vec<Card>) {
-    let mut chance = create_chance_deck();
-    let mut community_chest = create_community_chest_deck();
+/// Decks shipped with the binary, used when no deck files are found on disk
+const DEFAULT_DECKS_JSON: &str = include_str!("../../decks/keliling_ui.json");
+pub const DEFAULT_DECK_SET_ID: &str = "keliling_ui";
 
-    let mut rng = rng();
-    chance.shuffle(&mut rng);
-    community_chest.shuffle(&mut rng);
+/// The chance (SIAK-NG) and community chest (BEM) cards of one edition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeckSet {
+    pub id: String,
+    pub chance: Vec<Card>,
+    pub community_chest: Vec<Card>,
+}
 
-    (chance, community_chest)
+impl Default for DeckSet {
+    fn default() -> Self {
+        DeckSet::from_json(DEFAULT_DECKS_JSON).expect("built-in deck definition is valid")
+    }
 }
 
-fn create_chance_deck() -> Vec<Card> {
-    vec![
-        Card { id: 1, title: "IP Semester Naik!".to_string(), description: "Maju ke Wisuda (GO), terima Rp 200.000".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(0) },
-        Card { id: 2, title: "Lolos SNMPTN Kedokteran".to_string(), description: "Maju ke Kedokteran".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(39) },
-        Card { id: 3, title: "Pindah ke Ilmu Komputer".to_string(), description: "Maju ke Ilmu Komputer".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(26) },
-        Card { id: 4, title: "Rapat BEM".to_string(), description: "Maju ke Pusgiwa (Free Parking)".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(20) },
-        Card { id: 5, title: "Naik Bikun".to_string(), description: "Maju ke Railroad terdekat".to_string(), effect_type: "advance_nearest_railroad".to_string(), value: None, target_id: None },
-        Card { id: 6, title: "Ke Perpustakaan".to_string(), description: "Maju ke Utility terdekat".to_string(), effect_type: "advance_nearest_utility".to_string(), value: None, target_id: None },
-        Card { id: 7, title: "Dapat Beasiswa".to_string(), description: "Terima Rp 150.000".to_string(), effect_type: "collect".to_string(), value: Some(150_000), target_id: None },
-        Card { id: 8, title: "Menang Lomba Karya Tulis".to_string(), description: "Terima Rp 100.000".to_string(), effect_type: "collect".to_string(), value: Some(100_000), target_id: None },
-        Card { id: 9, title: "SIAK Error".to_string(), description: "Mundur 3 langkah".to_string(), effect_type: "back".to_string(), value: Some(3), target_id: None },
-        Card { id: 10, title: "Ketahuan Titip Absen".to_string(), description: "Langsung ke Skorsing".to_string(), effect_type: "go_to_jail".to_string(), value: None, target_id: None },
-        Card { id: 11, title: "Renovasi Kosan".to_string(), description: "Bayar Rp 25.000/Gedung, Rp 100.000/Fakultas".to_string(), effect_type: "repair".to_string(), value: Some(25_000), target_id: None },
-        Card { id: 12, title: "Tilang Parkir Liar".to_string(), description: "Bayar Rp 15.000".to_string(), effect_type: "pay".to_string(), value: Some(15_000), target_id: None },
-        Card { id: 13, title: "Maju ke Gerbang Utama".to_string(), description: "Maju ke Gerbang Utama".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(25) },
-        Card { id: 14, title: "Maju ke Akuntansi".to_string(), description: "Maju ke Akuntansi".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(21) },
-        Card { id: 15, title: "Kartu Bebas Skorsing".to_string(), description: "Simpan untuk keluar dari Skorsing".to_string(), effect_type: "get_out_of_jail".to_string(), value: None, target_id: None },
-        Card { id: 16, title: "Bayar SPP Tambahan".to_string(), description: "Bayar Rp 50.000".to_string(), effect_type: "pay".to_string(), value: Some(50_000), target_id: None },
-    ]
+impl DeckSet {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let decks: DeckSet = serde_json::from_str(json).map_err(|e| format!("Invalid deck definition: {}", e))?;
+        if decks.chance.is_empty() || decks.community_chest.is_empty() {
+            return Err("Both decks need at least one card".to_string());
+        }
+        Ok(decks)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        DeckSet::from_json(&json)
+    }
+
+    /// Loads every `*.json` deck set in `dir`, keyed by id. Invalid files are
+    /// skipped with a warning; the built-in decks are always available.
+    pub fn load_dir(dir: &Path) -> HashMap<String, DeckSet> {
+        let mut decks = HashMap::new();
+        let default = DeckSet::default();
+        decks.insert(default.id.clone(), default);
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Deck directory {} not readable ({}), using built-in decks", dir.display(), e);
+                return decks;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match DeckSet::load(&path) {
+                Ok(deck_set) => {
+                    tracing::info!("Loaded decks '{}' from {}", deck_set.id, path.display());
+                    decks.insert(deck_set.id.clone(), deck_set);
+                }
+                Err(e) => tracing::warn!("Skipping decks {}: {}", path.display(), e),
+            }
+        }
+        decks
+    }
 }
 
-fn create_community_chest_deck() -> Vec<Card> {
-    vec![
-        Card { id: 1, title: "Dana Kemahasiswaan".to_string(), description: "Terima Rp 200.000".to_string(), effect_type: "collect".to_string(), value: Some(200_000), target_id: None },
-        Card { id: 2, title: "Salah Transfer UKT".to_string(), description: "Terima Rp 75.000".to_string(), effect_type: "collect".to_string(), value: Some(75_000), target_id: None },
-        Card { id: 3, title: "Ospek Selesai".to_string(), description: "Terima Rp 50.000".to_string(), effect_type: "collect".to_string(), value: Some(50_000), target_id: None },
-        Card { id: 4, title: "Konsultasi ke Dokter Kampus".to_string(), description: "Bayar Rp 50.000".to_string(), effect_type: "pay".to_string(), value: Some(50_000), target_id: None },
-        Card { id: 5, title: "Iuran Makrab".to_string(), description: "Bayar Rp 25.000".to_string(), effect_type: "pay".to_string(), value: Some(25_000), target_id: None },
-        Card { id: 6, title: "Menang Lomba UI".to_string(), description: "Terima Rp 100.000".to_string(), effect_type: "collect".to_string(), value: Some(100_000), target_id: None },
-        Card { id: 7, title: "Refund UKT".to_string(), description: "Terima Rp 20.000".to_string(), effect_type: "collect".to_string(), value: Some(20_000), target_id: None },
-        Card { id: 8, title: "Ulang Tahun!".to_string(), description: "Terima Rp 10.000 dari setiap pemain".to_string(), effect_type: "collect_all".to_string(), value: Some(10_000), target_id: None },
-        Card { id: 9, title: "Asuransi Jatuh Tempo".to_string(), description: "Terima Rp 100.000".to_string(), effect_type: "collect".to_string(), value: Some(100_000), target_id: None },
-        Card { id: 10, title: "Bayar Jas Almamater".to_string(), description: "Bayar Rp 50.000".to_string(), effect_type: "pay".to_string(), value: Some(50_000), target_id: None },
-        Card { id: 11, title: "Hasil Jualan Makrab".to_string(), description: "Terima Rp 25.000".to_string(), effect_type: "collect".to_string(), value: Some(25_000), target_id: None },
-        Card { id: 12, title: "Kartu Bebas Skorsing".to_string(), description: "Simpan untuk keluar dari Skorsing".to_string(), effect_type: "get_out_of_jail".to_string(), value: None, target_id: None },
-        Card { id: 13, title: "Langsung ke Wisuda".to_string(), description: "Maju ke Wisuda (GO)".to_string(), effect_type: "advance".to_string(), value: None, target_id: Some(0) },
-        Card { id: 14, title: "Plagiarisme Terdeteksi".to_string(), description: "Langsung ke Skorsing".to_string(), effect_type: "go_to_jail".to_string(), value: None, target_id: None },
-        Card { id: 15, title: "Warisan dari Senior".to_string(), description: "Terima Rp 100.000".to_string(), effect_type: "collect".to_string(), value: Some(100_000), target_id: None },
-        Card { id: 16, title: "Perbaikan Gedung Fakultas".to_string(), description: "Bayar Rp 40.000/Gedung, Rp 115.000/Fakultas".to_string(), effect_type: "repair".to_string(), value: Some(40_000), target_id: None },
-    ]
+pub fn initialize_decks(decks: &DeckSet, rng: &mut GameRng) -> (Vec<Card>, Vec<Card>) {
+    let mut chance = decks.chance.clone();
+    let mut community_chest = decks.community_chest.clone();
+
+    for card in chance.iter_mut() {
+        card.is_chance = true;
+    }
+    chance.shuffle(rng);
+    community_chest.shuffle(rng);
+
+    (chance, community_chest)
 }
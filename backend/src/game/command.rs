@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use crate::game::state::GameState;
+use crate::game::trade::TradeOffer;
+use crate::ws::messages::ServerMessage;
+
+/// Everything that can change a running game. Player commands come from
+/// `ClientMessage`, the rest from the room timer and kick votes. Applying the
+/// same commands to the same state always gives the same result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum GameCommand {
+    RollDice,
+    BuyProperty,
+    DeclineProperty,
+    PlaceBid { amount: i32 },
+    PassAuction,
+    PayRent,
+    PayDebt,
+    DeclareBankruptcy,
+    EndTurn,
+    PayBail,
+    UseJailCard,
+    ProposeTrade { target_player_id: String, offer: TradeOffer, request: TradeOffer },
+    AcceptTrade { trade_id: String },
+    RejectTrade { trade_id: String },
+    CancelTrade { trade_id: String },
+    /// Answers a trade offered to the player with a revised one
+    CounterTrade { trade_id: String, offer: TradeOffer, request: TradeOffer },
+    BuyBuilding { property_id: usize },
+    SellBuilding { property_id: usize },
+    MortgageProperty { property_id: usize },
+    UnmortgageProperty { property_id: usize },
+    /// Lifts or keeps the mortgage on a property received in a trade
+    ResolveMortgage { property_id: usize, unmortgage: bool },
+    /// The turn timer ran out for the acting player
+    TurnTimeout,
+    /// The running auction passed its deadline
+    AuctionExpired,
+    /// Trade proposals passed their deadline
+    TradesExpired,
+    /// Puts the next estate returned by a bankruptcy up for auction
+    AuctionBankEstate,
+    /// A player was kicked out of the room or left it
+    RemovePlayer { player_id: String },
+    /// A bot takes over the seat of a player who was kicked out
+    ReplaceWithBot { player_id: String, bot_id: String, bot_name: String },
+    /// Debug mode: fixes the outcome of the next rolls
+    ScriptDice { dice: Vec<(u8, u8)> },
+}
+
+impl GameState {
+    /// Applies a command on behalf of `actor` (`None` for commands issued by
+    /// the server itself) at Unix time `now`, returning the events to broadcast.
+    pub fn apply(&mut self, actor: Option<&str>, command: &GameCommand, now: u64) -> Result<Vec<ServerMessage>, String> {
+        let player = || actor.ok_or_else(|| format!("{:?} needs a player", command));
+
+        match command {
+            GameCommand::RollDice => {
+                let (dice, events) = self.handle_roll(player()?)?;
+                let mut response = vec![ServerMessage::DiceRolled { dice, state: self.clone() }];
+                if !events.is_empty() {
+                    response.extend(events);
+                    response.push(ServerMessage::GameStateUpdate { state: self.clone() });
+                }
+                Ok(response)
+            },
+            GameCommand::BuyProperty => {
+                crate::game::actions::handle_buy_property(self, player()?)?;
+                Ok(vec![ServerMessage::GameStateUpdate { state: self.clone() }])
+            },
+            GameCommand::DeclineProperty => self.handle_decline_property(player()?, now),
+            GameCommand::PlaceBid { amount } => self.handle_place_bid(player()?, *amount, now),
+            GameCommand::PassAuction => self.handle_pass_auction(player()?),
+            GameCommand::PayRent => {
+                let mut events = crate::game::actions::handle_pay_rent(self, player()?)?;
+                events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+                Ok(events)
+            },
+            GameCommand::PayDebt => self.handle_pay_debt(player()?),
+            GameCommand::DeclareBankruptcy => self.handle_declare_bankruptcy(player()?),
+            GameCommand::EndTurn => {
+                self.next_turn(player()?)?;
+                Ok(vec![ServerMessage::TurnEnded { state: self.clone() }])
+            },
+            GameCommand::PayBail => {
+                self.check_turn(player()?)?;
+                let mut events = self.pay_bail(self.current_turn)?;
+                events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+                Ok(events)
+            },
+            GameCommand::UseJailCard => {
+                self.check_turn(player()?)?;
+                let mut events = self.use_jail_card(self.current_turn)?;
+                events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+                Ok(events)
+            },
+            GameCommand::ProposeTrade { target_player_id, offer, request } => {
+                self.handle_propose_trade(player()?.to_string(), target_player_id.clone(), offer.clone(), request.clone(), now)
+            },
+            GameCommand::CounterTrade { trade_id, offer, request } => {
+                self.handle_counter_trade(trade_id.clone(), player()?.to_string(), offer.clone(), request.clone(), now)
+            },
+            GameCommand::AcceptTrade { trade_id } => self.handle_accept_trade(trade_id.clone(), player()?.to_string()),
+            GameCommand::RejectTrade { trade_id } => self.handle_reject_trade(trade_id.clone(), player()?.to_string()),
+            GameCommand::CancelTrade { trade_id } => self.handle_cancel_trade(trade_id.clone(), player()?.to_string()),
+            GameCommand::BuyBuilding { property_id } => self.handle_buy_building(player()?.to_string(), *property_id),
+            GameCommand::SellBuilding { property_id } => self.handle_sell_building(player()?.to_string(), *property_id),
+            GameCommand::MortgageProperty { property_id } => self.handle_mortgage_property(player()?.to_string(), *property_id),
+            GameCommand::UnmortgageProperty { property_id } => self.handle_unmortgage_property(player()?.to_string(), *property_id),
+            GameCommand::ResolveMortgage { property_id, unmortgage } => self.handle_resolve_mortgage(player()?, *property_id, *unmortgage),
+            GameCommand::TurnTimeout => Ok(self.handle_turn_timeout(now)),
+            GameCommand::AuctionExpired => Ok(self.resolve_expired_auction(now)),
+            GameCommand::TradesExpired => Ok(self.expire_trades(now)),
+            GameCommand::AuctionBankEstate => self.auction_bank_estate(now),
+            GameCommand::RemovePlayer { player_id } => {
+                let mut events = self.remove_player(player_id);
+                events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+                Ok(events)
+            },
+            GameCommand::ReplaceWithBot { player_id, bot_id, bot_name } => {
+                let mut events = self.hand_seat_to_bot(player_id, bot_id, bot_name)?;
+                events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+                Ok(events)
+            },
+            GameCommand::ScriptDice { dice } => {
+                self.rng.script_dice(dice)?;
+                Ok(Vec::new())
+            },
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use crate::game::state::GamePhase;
+
+/// Who receives a payment once it is settled
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "player_id")]
+pub enum Creditor {
+    Bank,
+    /// The Free Parking jackpot (house rule)
+    Pot,
+    Player(String),
+}
+
+impl Creditor {
+    pub fn player_id(&self) -> Option<&str> {
+        match self {
+            Creditor::Player(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// A payment a player could not cover with cash. The debtor has to raise the
+/// money (mortgages, selling buildings, trades) or declare bankruptcy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Debt {
+    pub debtor_id: String,
+    pub creditor: Creditor,
+    pub amount: i32,
+    pub return_phase: GamePhase, // Phase to resume once every debt is settled
+}
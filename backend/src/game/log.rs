@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use crate::game::board::Board;
+use crate::game::command::GameCommand;
+use crate::game::state::GameState;
+use crate::ws::messages::ServerMessage;
+
+/// One accepted command and what it produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub seq: u64,
+    pub at: u64, // Unix timestamp in seconds, passed back to the rules on replay
+    pub player_id: Option<String>,
+    pub command: GameCommand,
+    /// Resulting events. Full state snapshots are left out since replay rebuilds them.
+    pub events: Vec<serde_json::Value>,
+}
+
+impl LogEntry {
+    pub fn new(seq: u64, at: u64, player_id: Option<&str>, command: GameCommand, events: &[ServerMessage]) -> Self {
+        let events = events.iter()
+            .filter_map(|event| serde_json::to_value(event).ok())
+            .map(|mut value| {
+                if let Some(fields) = value.as_object_mut() {
+                    fields.remove("state");
+                }
+                value
+            })
+            .collect();
+        Self { seq, at, player_id: player_id.map(|s| s.to_string()), command, events }
+    }
+}
+
+/// A line of the game log: a game starting, or a command applied to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "record")]
+pub enum LogRecord {
+    /// The board is stored alongside the starting state, which leaves it out,
+    /// so the game can be replayed after its room has closed
+    Started {
+        seed: u64,
+        initial_state: Box<GameState>,
+        #[serde(default)]
+        board: Box<Board>,
+    },
+    Command(LogEntry),
+}
+
+/// Everything needed to rebuild a game: its starting state (which carries
+/// the RNG seed) and every command applied since
+#[derive(Debug, Clone)]
+pub struct GameLog {
+    pub seed: u64,
+    pub initial_state: GameState,
+    pub entries: Vec<LogEntry>,
+}
+
+impl GameLog {
+    /// Builds the log of the latest game from stored records
+    pub fn from_records(records: impl IntoIterator<Item = LogRecord>) -> Result<Self, String> {
+        let mut log: Option<GameLog> = None;
+        for record in records {
+            match record {
+                LogRecord::Started { seed, initial_state, board } => {
+                    let initial_state = GameState { board: *board, ..*initial_state };
+                    log = Some(GameLog { seed, initial_state, entries: Vec::new() });
+                }
+                LogRecord::Command(entry) => {
+                    log.as_mut().ok_or("Log has commands before the game started")?.entries.push(entry);
+                }
+            }
+        }
+        log.ok_or_else(|| "No game has been started".to_string())
+    }
+
+    /// Rebuilds the state after the entries with `seq < upto` (all entries
+    /// when `upto` is `None`)
+    pub fn replay(&self, upto: Option<u64>) -> Result<GameState, String> {
+        let mut state = self.initial_state.clone();
+        for entry in self.entries.iter().filter(|e| upto.is_none_or(|upto| e.seq < upto)) {
+            state.apply(entry.player_id.as_deref(), &entry.command, entry.at)
+                .map_err(|e| format!("Replay diverged at entry {} ({:?}): {}", entry.seq, entry.command, e))?;
+        }
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::{GamePhase, PlayerState};
+
+    #[test]
+    fn test_replay_rebuilds_state() {
+        let mut game = GameState::new();
+        game.players = vec![PlayerState::new("a", "a", 1500000), PlayerState::new("b", "b", 1500000)];
+        game.phase = GamePhase::Rolling;
+
+        let mut log = GameLog { seed: game.rng.seed(), initial_state: game.clone(), entries: Vec::new() };
+        for seq in 0..30 {
+            let at = 1000 + seq * 20;
+            let (actor, command) = match game.phase {
+                GamePhase::Rolling if !game.offer_pending() => (game.acting_player_id().map(|id| id.to_string()), GameCommand::RollDice),
+                GamePhase::Auction => (None, GameCommand::AuctionExpired),
+                _ => (None, GameCommand::TurnTimeout),
+            };
+            let events = game.apply(actor.as_deref(), &command, at).unwrap();
+            log.entries.push(LogEntry::new(seq, at, actor.as_deref(), command, &events));
+        }
+
+        let replayed = log.replay(None).unwrap();
+        assert_eq!(serde_json::to_value(&replayed).unwrap(), serde_json::to_value(&game).unwrap());
+
+        let first = log.replay(Some(1)).unwrap();
+        assert_eq!(first.last_dice_roll, log.initial_state.clone().rng.roll_dice().into());
+    }
+}
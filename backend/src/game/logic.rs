@@ -1,12 +1,9 @@
-use rand::Rng;
-use crate::game::state::{GameState, GamePhase, Card};
+use crate::game::state::{GameState, GamePhase, PlayerState, Card, CardEffect, RentOverride};
+use crate::game::board::{PropertyGroup, TileKind};
+use crate::game::debt::{Creditor, Debt};
+use crate::game::rules::EstatePolicy;
 use crate::ws::messages::ServerMessage;
 
-pub fn roll_dice() -> (u8, u8) {
-    let mut rng = rand::rng();
-    (rng.random_range(1..=6), rng.random_range(1..=6))
-}
-
 impl GameState {
     pub fn next_turn(&mut self, player_id: &str) -> Result<(), String> {
         let old_phase = self.phase.clone();
@@ -19,10 +16,14 @@ impl GameState {
         
         self.check_turn(player_id)?;
         self.check_phase(GamePhase::EndTurn)?;
+        self.check_mortgage_decisions(player_id)?;
+        self.check_no_bank_auctions()?;
 
         self.current_turn = (self.current_turn + 1) % self.players.len();
         self.phase = GamePhase::Rolling;
         self.rent_paid = false;
+        self.offer_declined = true; // Last turn's tile is no longer on offer
+        self.rent_override = None;
         
         // Reset doubles count for the new player
         if let Some(player) = self.players.get_mut(self.current_turn) {
@@ -47,18 +48,31 @@ impl GameState {
         
         self.check_turn(player_id)?;
         self.check_phase(GamePhase::Rolling)?;
+        self.check_mortgage_decisions(player_id)?;
+        self.check_no_bank_auctions()?;
+        if self.offer_pending() {
+            return Err("Buy or decline the property before rolling again".to_string());
+        }
 
-        let dice = roll_dice();
+        let dice = self.rng.roll_dice();
         self.last_dice_roll = Some(dice);
         let is_double = dice.0 == dice.1;
         let mut events = Vec::new();
         let player_index = self.current_turn;
+        let mut forced_bail = false;
         
         tracing::info!(
             "[FSM] handle_roll: DICE - dice=({}, {}), is_double={}, player_index={}",
             dice.0, dice.1, is_double, player_index
         );
 
+        // Doubles earn another roll; otherwise the turn moves on to EndTurn.
+        // Set before landing so debts and jail resolve back to the right phase.
+        if !is_double {
+            self.phase = GamePhase::EndTurn;
+            tracing::info!("[FSM] handle_roll: TRANSITION - phase: Rolling -> EndTurn (not doubles)");
+        }
+
         if let Some(player) = self.players.get_mut(player_index) {
             if player.is_in_jail {
                 if is_double {
@@ -74,27 +88,15 @@ impl GameState {
                 } else {
                     player.jail_turns += 1;
                     if player.jail_turns >= 3 {
-                        // Forced bail after 3 turns - pay 50k and release
-                        if player.money >= 50_000 {
-                            player.money -= 50_000;
-                            player.is_in_jail = false;
-                            player.jail_turns = 0;
-                            events.push(ServerMessage::JailStateUpdated { 
-                                player_id: player.id.clone(), 
-                                is_in_jail: false, 
-                                jail_turns: 0 
-                            });
-                        } else {
-                            // Cannot afford bail - release into debt
-                             player.money -= 50_000;
-                             player.is_in_jail = false;
-                             player.jail_turns = 0;
-                             events.push(ServerMessage::JailStateUpdated { 
-                                 player_id: player.id.clone(), 
-                                 is_in_jail: false, 
-                                 jail_turns: 0 
-                             });
-                        }
+                        // Forced bail after 3 turns - release and charge bail (into debt if unaffordable)
+                        player.is_in_jail = false;
+                        player.jail_turns = 0;
+                        forced_bail = true;
+                        events.push(ServerMessage::JailStateUpdated { 
+                            player_id: player.id.clone(), 
+                            is_in_jail: false, 
+                            jail_turns: 0 
+                        });
                     } else {
                          events.push(ServerMessage::JailStateUpdated { 
                             player_id: player.id.clone(), 
@@ -117,6 +119,11 @@ impl GameState {
             }
         }
         
+        if forced_bail {
+            let bail_amount = self.rules.bail_amount;
+            events.extend(self.charge(player_index, bail_amount, Creditor::Bank));
+        }
+
         // Re-borrow to handle movement and logic
         let mut sent_to_jail = false;
         if let Some(player) = self.players.get(player_index) {
@@ -152,14 +159,27 @@ impl GameState {
     }
 
     pub fn move_player(&mut self, player_index: usize, steps: u8) {
+        let board_size = self.board.size();
+        let go_salary = self.rules.go_salary;
         if let Some(player) = self.players.get_mut(player_index) {
-            let old_pos = player.position;
-            player.position = (player.position + steps as usize) % 40;
+            let new_pos = player.position + steps as usize;
+            player.position = new_pos % board_size;
             
             // Pass GO logic
-            if player.position < old_pos {
-                player.money += 200_000;
+            if new_pos >= board_size {
+                player.money += go_salary;
+            }
+        }
+    }
+
+    /// Moves a player forward to `target`, collecting GO salary when wrapping around
+    pub fn advance_player_to(&mut self, player_index: usize, target: usize) {
+        let go_salary = self.rules.go_salary;
+        if let Some(player) = self.players.get_mut(player_index) {
+            if target < player.position {
+                player.money += go_salary; // Pass GO
             }
+            player.position = target;
         }
     }
 
@@ -168,33 +188,47 @@ impl GameState {
         let player_id = self.players[player_index].id.clone();
         let position = self.players[player_index].position;
 
-        match position {
+        // A new tile means a new rent obligation and a new purchase offer
+        self.rent_paid = false;
+        self.offer_declined = false;
+        self.rent_override = None;
+
+        let tile_kind = match self.board.tile(position) {
+            Some(tile) => tile.kind.clone(),
+            None => return events,
+        };
+
+        match tile_kind {
             // Community Chest (BEM)
-            2 | 17 | 33 => {
-                let card = self.draw_community_chest_card();
-                events.push(ServerMessage::CardDrawn { card: card.clone(), is_chance: false });
-                events.extend(self.apply_card_effect(player_index, &card));
+            TileKind::CommunityChest => {
+                if let Some(card) = self.draw_community_chest_card() {
+                    events.push(ServerMessage::CardDrawn { card: card.clone(), is_chance: false });
+                    events.extend(self.apply_card_effect(player_index, &card));
+                }
             },
             // Chance (SIAK-NG)
-            7 | 22 | 36 => {
-                let card = self.draw_chance_card();
-                events.push(ServerMessage::CardDrawn { card: card.clone(), is_chance: true });
-                events.extend(self.apply_card_effect(player_index, &card));
-            },
-            // Tax (Bayar UKT)
-            4 => {
-                if let Some(player) = self.players.get_mut(player_index) {
-                    player.money -= 200_000;
+            TileKind::Chance => {
+                if let Some(card) = self.draw_chance_card() {
+                    events.push(ServerMessage::CardDrawn { card: card.clone(), is_chance: true });
+                    events.extend(self.apply_card_effect(player_index, &card));
                 }
             },
-            // Tax (Biaya Praktikum)
-            38 => {
-                if let Some(player) = self.players.get_mut(player_index) {
-                    player.money -= 100_000;
-                }
+            // Tax (Bayar UKT, Biaya Praktikum)
+            TileKind::Tax { amount } => {
+                let due = self.rules.tax(amount);
+                events.extend(self.pay_to_bank(player_index, due));
+            },
+            // Free Parking (Pusgiwa)
+            TileKind::FreeParking if self.rules.free_parking_jackpot && self.free_parking_pot > 0 => {
+                let amount = std::mem::take(&mut self.free_parking_pot);
+                self.players[player_index].money += amount;
+                events.push(ServerMessage::FreeParkingPayout {
+                    player_id: player_id.clone(),
+                    amount,
+                });
             },
             // Go To Jail (Sanksi Akademik)
-            30 => {
+            TileKind::GoToJail => {
                 self.send_to_jail(player_index);
                 events.push(ServerMessage::JailStateUpdated { 
                     player_id: player_id.clone(), 
@@ -208,6 +242,135 @@ impl GameState {
         events
     }
 
+    /// Takes a tax or card payment from a player. With the Free Parking
+    /// jackpot rule the money goes into the pot instead of the bank.
+    fn pay_to_bank(&mut self, player_index: usize, amount: i32) -> Vec<ServerMessage> {
+        let creditor = if self.rules.free_parking_jackpot { Creditor::Pot } else { Creditor::Bank };
+        self.charge(player_index, amount, creditor)
+    }
+
+    /// Charges a player, paying the creditor straight away when the player has
+    /// the cash. Otherwise the payment becomes a debt and the game enters
+    /// DebtResolution until it is paid or the player goes bankrupt.
+    pub fn charge(&mut self, player_index: usize, amount: i32, creditor: Creditor) -> Vec<ServerMessage> {
+        let Some(player) = self.players.get_mut(player_index) else {
+            return Vec::new();
+        };
+        if amount <= 0 {
+            return Vec::new();
+        }
+        if player.money >= amount {
+            player.money -= amount;
+            self.credit(&creditor, amount);
+            return Vec::new();
+        }
+
+        let debt = Debt {
+            debtor_id: player.id.clone(),
+            creditor,
+            amount,
+            return_phase: match self.debts.first() {
+                Some(existing) => existing.return_phase.clone(),
+                None => self.phase.clone(),
+            },
+        };
+        tracing::info!(
+            "[FSM] charge: DEBT - debtor={}, creditor={:?}, amount={}, money={}",
+            debt.debtor_id, debt.creditor, debt.amount, player.money
+        );
+        self.debts.push(debt.clone());
+        self.phase = GamePhase::DebtResolution;
+        vec![ServerMessage::DebtIncurred { debt }]
+    }
+
+    fn credit(&mut self, creditor: &Creditor, amount: i32) {
+        match creditor {
+            Creditor::Bank => {},
+            Creditor::Pot => self.free_parking_pot += amount,
+            Creditor::Player(id) => {
+                if let Some(player) = self.players.iter_mut().find(|p| &p.id == id) {
+                    player.money += amount;
+                }
+            },
+        }
+    }
+
+    /// Pays off every debt of `player_id` once they have raised enough cash
+    pub fn handle_pay_debt(&mut self, player_id: &str) -> Result<Vec<ServerMessage>, String> {
+        self.check_phase(GamePhase::DebtResolution)?;
+        let player_idx = self.players.iter().position(|p| p.id == player_id).ok_or("Player not found")?;
+
+        let total: i32 = self.debts.iter().filter(|d| d.debtor_id == player_id).map(|d| d.amount).sum();
+        if total == 0 {
+            return Err("You have no debts".to_string());
+        }
+        if self.players[player_idx].money < total {
+            return Err(format!("Not enough money, {} needed", total));
+        }
+
+        let mut events = Vec::new();
+        let return_phase = self.debts[0].return_phase.clone();
+        let (paid, remaining): (Vec<Debt>, Vec<Debt>) = self.debts.drain(..).partition(|d| d.debtor_id == player_id);
+        self.debts = remaining;
+        for debt in paid {
+            self.players[player_idx].money -= debt.amount;
+            self.credit(&debt.creditor, debt.amount);
+            events.push(ServerMessage::DebtPaid {
+                debtor_id: debt.debtor_id,
+                creditor_id: debt.creditor.player_id().map(|s| s.to_string()),
+                amount: debt.amount,
+            });
+        }
+        self.resume_after_debts(return_phase);
+
+        events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+        Ok(events)
+    }
+
+    /// Declares bankruptcy on an outstanding debt. Assets go to the creditor
+    /// the player owes the most, or back to the bank.
+    pub fn handle_declare_bankruptcy(&mut self, player_id: &str) -> Result<Vec<ServerMessage>, String> {
+        let creditor = self.debts.iter()
+            .filter(|d| d.debtor_id == player_id)
+            .max_by_key(|d| d.amount)
+            .map(|d| d.creditor.clone())
+            .ok_or("You can only declare bankruptcy on an unpaid debt")?;
+
+        let was_current = self.players.get(self.current_turn).is_some_and(|p| p.id == player_id);
+        let mut events = self.handle_bankruptcy(player_id, creditor.player_id());
+
+        // The bankrupt player's turn is over; the next player starts rolling
+        if was_current && self.phase != GamePhase::GameOver && self.phase != GamePhase::DebtResolution {
+            self.phase = GamePhase::Rolling;
+            self.rent_paid = false;
+            self.offer_declined = true;
+            self.rent_override = None;
+            events.push(ServerMessage::TurnEnded { state: self.clone() });
+        }
+        Ok(events)
+    }
+
+    /// Drops debts owed by a player leaving the game; debts owed to them go to the bank
+    fn clear_debts_of(&mut self, player_id: &str) {
+        let Some(return_phase) = self.debts.first().map(|d| d.return_phase.clone()) else {
+            return;
+        };
+        self.debts.retain(|d| d.debtor_id != player_id);
+        for debt in self.debts.iter_mut() {
+            if debt.creditor.player_id() == Some(player_id) {
+                debt.creditor = Creditor::Bank;
+            }
+        }
+        self.resume_after_debts(return_phase);
+    }
+
+    fn resume_after_debts(&mut self, return_phase: GamePhase) {
+        if self.phase == GamePhase::DebtResolution && self.debts.is_empty() {
+            tracing::info!("[FSM] resume_after_debts: TRANSITION - phase: DebtResolution -> {:?}", return_phase);
+            self.phase = return_phase;
+        }
+    }
+
     pub fn send_to_jail(&mut self, player_index: usize) {
         let old_phase = self.phase.clone();
         let player_name = self.players.get(player_index)
@@ -219,8 +382,9 @@ impl GameState {
             player_index, player_name, self.phase
         );
         
+        let jail_position = self.board.jail_position();
         if let Some(player) = self.players.get_mut(player_index) {
-            player.position = 10; // Jail position
+            player.position = jail_position;
             player.is_in_jail = true;
             player.jail_turns = 0;
             player.doubles_count = 0;
@@ -233,67 +397,42 @@ impl GameState {
         );
     }
 
-    fn draw_chance_card(&mut self) -> Card {
+    fn draw_chance_card(&mut self) -> Option<Card> {
         if self.chance_deck.is_empty() {
-            let (new_chance, _) = crate::game::cards::initialize_decks();
-            self.chance_deck = new_chance;
+            return None; // Only happens while every card is a held jail card
         }
         let card = self.chance_deck.remove(0);
         self.chance_deck.push(card.clone()); // Put at bottom
-        card
+        Some(card)
     }
 
-    fn draw_community_chest_card(&mut self) -> Card {
+    fn draw_community_chest_card(&mut self) -> Option<Card> {
         if self.community_chest_deck.is_empty() {
-            let (_, new_cc) = crate::game::cards::initialize_decks();
-            self.community_chest_deck = new_cc;
+            return None;
         }
         let card = self.community_chest_deck.remove(0);
         self.community_chest_deck.push(card.clone()); // Put at bottom
-        card
+        Some(card)
     }
 
     fn apply_card_effect(&mut self, player_index: usize, card: &Card) -> Vec<ServerMessage> {
         let mut events = Vec::new();
         let player_id = self.players[player_index].id.clone();
 
-        match card.effect_type.as_str() {
-            "collect" => {
-                if let Some(amount) = card.value {
-                    if let Some(player) = self.players.get_mut(player_index) {
-                        player.money += amount;
-                    }
+        match &card.effect {
+            CardEffect::Collect { amount } => {
+                if let Some(player) = self.players.get_mut(player_index) {
+                    player.money += amount;
                 }
             },
-            "pay" => {
-                if let Some(amount) = card.value {
-                    if let Some(player) = self.players.get_mut(player_index) {
-                        player.money -= amount;
-                    }
-                }
+            CardEffect::Pay { amount } => {
+                events.extend(self.pay_to_bank(player_index, *amount));
             },
-            "advance" => {
-                if let Some(target) = card.target_id {
-                    if let Some(player) = self.players.get_mut(player_index) {
-                        let current = player.position;
-                        if target < current {
-                            player.money += 200_000; // Pass GO
-                        }
-                        player.position = target;
-                    }
-                    // Handle landing on new tile (recursive?)
-                    // For simplicity, we won't recurse deeply, but we should check if it's Go To Jail
-                    if card.target_id == Some(30) {
-                        self.send_to_jail(player_index);
-                        events.push(ServerMessage::JailStateUpdated { 
-                            player_id: player_id.clone(), 
-                            is_in_jail: true, 
-                            jail_turns: 0 
-                        });
-                    }
-                }
+            CardEffect::Advance { target } => {
+                self.advance_player_to(player_index, *target);
+                events.extend(self.handle_landing(player_index));
             },
-            "go_to_jail" => {
+            CardEffect::GoToJail => {
                 self.send_to_jail(player_index);
                 events.push(ServerMessage::JailStateUpdated { 
                     player_id: player_id.clone(), 
@@ -301,76 +440,54 @@ impl GameState {
                     jail_turns: 0 
                 });
             },
-            "back" => {
-                if let Some(steps) = card.value {
-                    if let Some(player) = self.players.get_mut(player_index) {
-                        // Handle wrap around for negative movement
-                        let pos = player.position as i32 - steps;
-                        player.position = if pos < 0 { (40 + pos) as usize } else { pos as usize };
-                    }
-                    // If landed on Go To Jail (30)
-                    if self.players[player_index].position == 30 {
-                        self.send_to_jail(player_index);
-                        events.push(ServerMessage::JailStateUpdated { 
-                            player_id: player_id.clone(), 
-                            is_in_jail: true, 
-                            jail_turns: 0 
-                        });
-                    }
+            CardEffect::Back { steps } => {
+                let board_size = self.board.size();
+                if let Some(player) = self.players.get_mut(player_index) {
+                    // Handle wrap around for negative movement (never collects GO)
+                    player.position = (player.position + board_size - steps % board_size) % board_size;
                 }
+                events.extend(self.handle_landing(player_index));
             },
-            "collect_all" => {
-                if let Some(amount) = card.value {
-                    let count = self.players.len();
-                    for i in 0..count {
-                        if i != player_index {
-                            self.players[i].money -= amount;
-                        }
+            CardEffect::CollectFromAll { amount } => {
+                for i in 0..self.players.len() {
+                    if i != player_index {
+                        events.extend(self.charge(i, *amount, Creditor::Player(player_id.clone())));
                     }
-                    self.players[player_index].money += amount * (count as i32 - 1);
                 }
             },
-            "advance_nearest_railroad" => {
-                if let Some(player) = self.players.get_mut(player_index) {
-                    let current = player.position;
-                    // Railroads: 5, 15, 25, 35
-                    let railroads = [5, 15, 25, 35];
-                    let mut target = 5;
-                    for &r in &railroads {
-                        if r > current {
-                            target = r;
-                            break;
-                        }
-                    }
-                    // If no railroad > current, wrap around to 5
-                    if target < current {
-                        player.money += 200_000;
-                    }
-                    player.position = target;
+            CardEffect::PayEachPlayer { amount } => {
+                let others: Vec<String> = self.players.iter()
+                    .filter(|p| p.id != player_id)
+                    .map(|p| p.id.clone())
+                    .collect();
+                for other_id in others {
+                    events.extend(self.charge(player_index, *amount, Creditor::Player(other_id)));
                 }
             },
-            "advance_nearest_utility" => {
-                if let Some(player) = self.players.get_mut(player_index) {
-                    let current = player.position;
-                    // Utilities: 12, 28
-                    let utilities = [12, 28];
-                    let mut target = 12;
-                    for &u in &utilities {
-                        if u > current {
-                            target = u;
-                            break;
+            CardEffect::AdvanceToNearest { group, rent_multiplier, rent_per_pip } => {
+                let current = self.players[player_index].position;
+                if let Some(target) = self.board.next_in_group(current, group) {
+                    self.advance_player_to(player_index, target);
+                    events.extend(self.handle_landing(player_index));
+                    // Set after landing, which clears any previous override
+                    self.rent_override = Some(match rent_per_pip {
+                        Some(per_pip) => RentOverride::PerDicePip(*per_pip),
+                        None => RentOverride::Multiplier(*rent_multiplier),
+                    });
+                    // The card's rent is owed at once, like a tax
+                    let owner_id = self.properties.iter().find(|p| p.id == target).and_then(|p| p.owner_id.clone());
+                    if let Some(owner_id) = owner_id.filter(|id| *id != player_id) {
+                        let dice_sum = self.last_dice_roll.map_or(0, |(d1, d2)| d1 + d2);
+                        let rent = self.rent_due(target, dice_sum);
+                        if rent > 0 {
+                            events.extend(self.charge(player_index, rent, Creditor::Player(owner_id)));
+                            self.rent_paid = true;
                         }
                     }
-                    if target < current {
-                        player.money += 200_000;
-                    }
-                    player.position = target;
                 }
             },
-            "repair" => {
+            CardEffect::Repair { per_house, per_hotel } => {
                 // Calculate repair costs based on buildings owned
-                // Re-borrow immutably to calculate
-                let player_id = self.players[player_index].id.clone();
                 let mut house_count = 0;
                 let mut hotel_count = 0;
                 
@@ -384,47 +501,31 @@ impl GameState {
                     }
                 }
                 
-                // Determine repair rate based on card type
-                
-                let (house_cost, hotel_cost) = if card.title == "Renovasi Kosan" {
-                    (25_000, 100_000)
-                } else {
-                    (40_000, 115_000)
-                };
-                
-                let total_cost = (house_count * house_cost) + (hotel_count * hotel_cost);
-                
-                if let Some(player) = self.players.get_mut(player_index) {
-                    player.money -= total_cost;
-                }
+                let total_cost = (house_count * per_house) + (hotel_count * per_hotel);
+                events.extend(self.pay_to_bank(player_index, total_cost));
             },
-            "get_out_of_jail" => {
+            CardEffect::GetOutOfJail => {
                 if let Some(player) = self.players.get_mut(player_index) {
                     player.held_cards.push(card.clone());
                 }
                 // Remove card from deck (it was pushed to bottom by draw function)
                 // If it's Chance...
-                if let Some(last) = self.chance_deck.last() {
-                    if last.id == card.id && last.title == card.title {
-                        self.chance_deck.pop();
-                    }
+                if self.chance_deck.last() == Some(card) {
+                    self.chance_deck.pop();
                 }
                 // If it's Community Chest...
-                if let Some(last) = self.community_chest_deck.last() {
-                    if last.id == card.id && last.title == card.title {
-                        self.community_chest_deck.pop();
-                    }
+                if self.community_chest_deck.last() == Some(card) {
+                    self.community_chest_deck.pop();
                 }
             },
-            _ => {}
         }
         events
     }
 
-    fn check_monopoly(&self, owner_id: &str, group: crate::game::board::PropertyGroup) -> bool {
+    pub fn check_monopoly(&self, owner_id: &str, group: PropertyGroup) -> bool {
         let group_properties: Vec<&crate::game::state::PropertyState> = self.properties.iter()
             .filter(|p| {
-                if let Some(prop_info) = crate::game::board::get_property(p.id) {
+                if let Some(prop_info) = self.board.property(p.id) {
                     prop_info.group == group
                 } else {
                     false
@@ -447,74 +548,63 @@ impl GameState {
             return 0;
         }
         
-        let prop_info = match crate::game::board::get_property(property_id) {
+        let prop_info = match self.board.property(property_id) {
             Some(info) => info,
             None => return 0,
         };
 
         if let Some(owner_id) = &property.owner_id {
-            // Check for utilities
-            if prop_info.group == crate::game::board::PropertyGroup::Utility {
-                let utility_count = self.properties.iter()
+            // Utilities and railroads scale with how many of the group the owner holds
+            if prop_info.group == PropertyGroup::Utility
+                || prop_info.group == PropertyGroup::Railroad
+            {
+                let owned_count = self.properties.iter()
                     .filter(|p| {
-                        if let Some(info) = crate::game::board::get_property(p.id) {
-                            info.group == crate::game::board::PropertyGroup::Utility && p.owner_id.as_deref() == Some(owner_id)
+                        if let Some(info) = self.board.property(p.id) {
+                            info.group == prop_info.group && p.owner_id.as_deref() == Some(owner_id)
                         } else {
                             false
                         }
                     })
                     .count();
-                
-                return if utility_count == 2 {
-                    (dice_roll as i32) * 10000
+                let rent = prop_info.rent.get(owned_count.saturating_sub(1)).copied().unwrap_or(0);
+
+                return if prop_info.group == PropertyGroup::Utility {
+                    (dice_roll as i32) * rent
                 } else {
-                    (dice_roll as i32) * 4000
+                    rent
                 };
             }
 
-            // Check for railroads
-            if prop_info.group == crate::game::board::PropertyGroup::Railroad {
-                let railroad_count = self.properties.iter()
-                    .filter(|p| {
-                        if let Some(info) = crate::game::board::get_property(p.id) {
-                            info.group == crate::game::board::PropertyGroup::Railroad && p.owner_id.as_deref() == Some(owner_id)
-                        } else {
-                            false
-                        }
-                    })
-                    .count();
-                
-                return match railroad_count {
-                    1 => 25000,
-                    2 => 50000,
-                    3 => 100000,
-                    4 => 200000,
-                    _ => 0,
-                };
+            // Standard properties: rent[1..=5] for 1-4 houses or a hotel
+            if property.houses > 0 {
+                return prop_info.rent[property.houses.min(5) as usize];
             }
 
-            // Standard properties
-            let base_rent = prop_info.rent;
-            
-            // If has houses, calculate based on house count
-            // Placeholder for house rent logic until board.rs is updated
-            
-            // Let's check if monopoly
-            if self.check_monopoly(owner_id, prop_info.group.clone()) && property.houses == 0 {
-                return base_rent * 2;
-            }
-            
-            if property.houses > 0 {
-                 // Simple multiplier for now until we add full data
-                 return base_rent * (1 + property.houses as i32 * 4); 
+            // Unimproved monopoly doubles the base rent
+            if self.check_monopoly(owner_id, prop_info.group.clone()) {
+                return prop_info.rent[0] * 2;
             }
 
-            base_rent
+            prop_info.rent[0]
         } else {
             0
         }
     }
 
+    /// Rent owed for landing on `property_id`, including any card-imposed rule
+    pub fn rent_due(&self, property_id: usize, dice_roll: u8) -> i32 {
+        let rent = self.calculate_rent(property_id, dice_roll);
+        if rent == 0 {
+            return 0; // Unowned or mortgaged
+        }
+        match self.rent_override {
+            Some(RentOverride::Multiplier(multiplier)) => rent * multiplier,
+            Some(RentOverride::PerDicePip(per_pip)) => dice_roll as i32 * per_pip,
+            None => rent,
+        }
+    }
+
     pub fn handle_buy_building(&mut self, player_id: String, property_id: usize) -> Result<Vec<ServerMessage>, String> {
         let property = self.properties.iter().find(|p| p.id == property_id).ok_or("Property not found")?;
         
@@ -522,10 +612,10 @@ impl GameState {
             return Err("You do not own this property".to_string());
         }
 
-        let prop_info = crate::game::board::get_property(property_id).ok_or("Cannot build on this property")?;
+        let prop_info = self.board.property(property_id).cloned().ok_or("Cannot build on this property")?;
         let group = prop_info.group.clone();
         
-        if group == crate::game::board::PropertyGroup::Railroad || group == crate::game::board::PropertyGroup::Utility {
+        if group == PropertyGroup::Railroad || group == PropertyGroup::Utility {
             return Err("Cannot build on railroads or utilities".to_string());
         }
 
@@ -536,7 +626,7 @@ impl GameState {
         // Check even building
         let group_properties: Vec<&crate::game::state::PropertyState> = self.properties.iter()
             .filter(|p| {
-                if let Some(info) = crate::game::board::get_property(p.id) {
+                if let Some(info) = self.board.property(p.id) {
                     info.group == group
                 } else {
                     false
@@ -566,13 +656,7 @@ impl GameState {
         }
 
         // Cost
-        let building_cost = match property_id {
-            1 | 3 | 6 | 8 | 9 => 50000,
-            11 | 13 | 14 | 16 | 18 | 19 => 100000,
-            21 | 23 | 24 | 26 | 27 | 29 => 150000,
-            31 | 32 | 34 | 37 | 39 => 200000,
-            _ => 0,
-        };
+        let building_cost = prop_info.house_cost;
 
         let player_idx = self.players.iter().position(|p| p.id == player_id).unwrap();
         if self.players[player_idx].money < building_cost {
@@ -612,13 +696,13 @@ impl GameState {
             return Err("No buildings to sell".to_string());
         }
 
-        let prop_info = crate::game::board::get_property(property_id).ok_or("Invalid property")?;
+        let prop_info = self.board.property(property_id).cloned().ok_or("Invalid property")?;
         let group = prop_info.group.clone();
 
         // Check even selling
         let group_properties: Vec<&crate::game::state::PropertyState> = self.properties.iter()
             .filter(|p| {
-                if let Some(info) = crate::game::board::get_property(p.id) {
+                if let Some(info) = self.board.property(p.id) {
                     info.group == group
                 } else {
                     false
@@ -632,15 +716,13 @@ impl GameState {
             return Err("Must sell evenly".to_string());
         }
 
+        // Check if there are enough houses in the bank to replace a hotel
+        if property.houses == 5 && self.total_houses < 4 {
+            return Err("Not enough houses in bank to downgrade hotel".to_string());
+        }
+
         // Cost (50% return)
-        let building_cost = match property_id {
-            1 | 3 | 6 | 8 | 9 => 50000,
-            11 | 13 | 14 | 16 | 18 | 19 => 100000,
-            21 | 23 | 24 | 26 | 27 | 29 => 150000,
-            31 | 32 | 34 | 37 | 39 => 200000,
-            _ => 0,
-        };
-        let refund = building_cost / 2;
+        let refund = prop_info.house_cost / 2;
 
         // Execute
         let player_idx = self.players.iter().position(|p| p.id == player_id).unwrap();
@@ -649,10 +731,6 @@ impl GameState {
         let prop_mut = self.properties.iter_mut().find(|p| p.id == property_id).unwrap();
         
         if prop_mut.houses == 5 { // Selling a hotel
-            // Check if there are enough houses in the bank to replace the hotel
-            if self.total_houses < 4 {
-                return Err("Not enough houses in bank to downgrade hotel".to_string());
-            }
             self.total_hotels += 1; // Return 1 hotel to the bank
             self.total_houses -= 4; // Take 4 houses from the bank
         } else { // Selling a house
@@ -682,8 +760,8 @@ impl GameState {
             return Err("Must sell all buildings before mortgaging".to_string());
         }
         
-        let prop_info = crate::game::board::get_property(property_id).ok_or("Property not found")?;
-        let mortgage_value = prop_info.price / 2;
+        let prop_info = self.board.property(property_id).cloned().ok_or("Property not found")?;
+        let mortgage_value = prop_info.mortgage_value;
         
         let player_idx = self.players.iter().position(|p| p.id == player_id).unwrap();
         self.players[player_idx].money += mortgage_value;
@@ -708,8 +786,8 @@ impl GameState {
             return Err("Property is not mortgaged".to_string());
         }
         
-        let prop_info = crate::game::board::get_property(property_id).ok_or("Property not found")?;
-        let unmortgage_cost = (prop_info.price / 2) + (prop_info.price / 20); // 50% + 10% = 110% of mortgage value
+        let prop_info = self.board.property(property_id).cloned().ok_or("Property not found")?;
+        let unmortgage_cost = prop_info.mortgage_value + prop_info.mortgage_value / 10; // 110% of mortgage value
         
         let player_idx = self.players.iter().position(|p| p.id == player_id).unwrap();
         if self.players[player_idx].money < unmortgage_cost {
@@ -720,6 +798,7 @@ impl GameState {
         
         let prop_mut = self.properties.iter_mut().find(|p| p.id == property_id).unwrap();
         prop_mut.is_mortgaged = false;
+        self.mortgage_decisions.retain(|d| d.property_id != property_id);
         
         Ok(vec![
             ServerMessage::PropertyUnmortgaged { property_id, cost: unmortgage_cost },
@@ -735,26 +814,59 @@ impl GameState {
             .map(|p| p.name.clone())
             .unwrap_or_else(|| "Unknown".to_string());
         
-        // Transfer all properties to creditor, or return to bank
-        for property in self.properties.iter_mut() {
-            if property.owner_id.as_deref() == Some(bankrupt_player_id) {
-                if let Some(cred_id) = creditor_id {
-                    // Transfer to creditor (mortgages stay as-is per user decision)
-                    property.owner_id = Some(cred_id.to_string());
+        let proceeds = self.sell_buildings_to_bank(bankrupt_player_id);
+        let (cash, held_cards) = match self.players.iter_mut().find(|p| p.id == bankrupt_player_id) {
+            Some(player) => (player.money + proceeds, std::mem::take(&mut player.held_cards)),
+            None => (proceeds, Vec::new()),
+        };
+
+        let estates: Vec<usize> = self.properties.iter()
+            .filter(|p| p.owner_id.as_deref() == Some(bankrupt_player_id))
+            .map(|p| p.id)
+            .collect();
+        let creditor = creditor_id.and_then(|id| self.players.iter_mut().find(|p| p.id == id));
+        if let Some(creditor) = creditor {
+            // The creditor takes the cash, jail cards and estates, and settles
+            // the interest on each mortgaged one
+            creditor.money += cash.max(0);
+            creditor.held_cards.extend(held_cards);
+            let creditor_id = creditor.id.clone();
+            for property_id in estates {
+                let property = self.properties.iter_mut().find(|p| p.id == property_id).unwrap();
+                property.owner_id = Some(creditor_id.clone());
+                if property.is_mortgaged {
+                    events.push(self.require_mortgage_decision(creditor_id.clone(), property_id));
+                }
+            }
+        } else {
+            // The bank cancels the mortgages and auctions the estates
+            for property in self.properties.iter_mut().filter(|p| estates.contains(&p.id)) {
+                property.owner_id = None;
+                property.is_mortgaged = false;
+            }
+            self.pending_auctions.extend(estates);
+            // Held cards go back to the bottom of their decks
+            for card in held_cards {
+                if card.is_chance {
+                    self.chance_deck.push(card);
                 } else {
-                    // Return to bank
-                    property.owner_id = None;
-                    property.is_mortgaged = false;
-                    property.houses = 0;
+                    self.community_chest_deck.push(card);
                 }
             }
         }
         
+        events.extend(self.remove_auction_bidder(bankrupt_player_id));
+        self.clear_debts_of(bankrupt_player_id);
+        self.mortgage_decisions.retain(|d| d.player_id != bankrupt_player_id);
+
         // Remove bankrupt player from game
         if let Some(idx) = self.players.iter().position(|p| p.id == bankrupt_player_id) {
             self.players.remove(idx);
             
             // Adjust current turn
+            if idx < self.current_turn {
+                self.current_turn -= 1;
+            }
             if !self.players.is_empty() {
                 self.current_turn = self.current_turn % self.players.len();
             }
@@ -773,6 +885,23 @@ impl GameState {
         events
     }
 
+    /// Returns every building of the player to the bank's stock, giving back
+    /// the half-price proceeds
+    fn sell_buildings_to_bank(&mut self, player_id: &str) -> i32 {
+        let mut proceeds = 0;
+        for property in self.properties.iter_mut().filter(|p| p.owner_id.as_deref() == Some(player_id) && p.houses > 0) {
+            let house_cost = self.board.property(property.id).map(|info| info.house_cost).unwrap_or(0);
+            proceeds += property.houses as i32 * house_cost / 2;
+            if property.houses == 5 {
+                self.total_hotels += 1;
+            } else {
+                self.total_houses += property.houses;
+            }
+            property.houses = 0;
+        }
+        proceeds
+    }
+
     pub fn check_victory(&mut self) -> Vec<ServerMessage> {
         if self.players.len() == 1 {
             let winner = &self.players[0];
@@ -787,39 +916,136 @@ impl GameState {
         Vec::new()
     }
 
-    pub fn remove_player(&mut self, player_id: &str) {
-        // Return properties to bank (Rektorat)
-        for property in self.properties.iter_mut() {
-            if let Some(owner) = &property.owner_id {
-                if owner == player_id {
-                    property.owner_id = None;
-                }
-            }
+    /// Takes a kicked or departing player out of the game. Their estates go
+    /// back to the bank (Rektorat) with buildings returned to its stock and
+    /// mortgages cleared, and are auctioned under `EstatePolicy::Auction`.
+    /// Their open trades are cancelled and, if it was their turn, the next
+    /// player starts rolling.
+    pub fn remove_player(&mut self, player_id: &str) -> Vec<ServerMessage> {
+        let was_current = self.players.get(self.current_turn).is_some_and(|p| p.id == player_id);
+        let mut events = self.cancel_trades_of(player_id);
+        events.extend(self.remove_auction_bidder(player_id));
+        self.clear_debts_of(player_id);
+        self.mortgage_decisions.retain(|d| d.player_id != player_id);
+
+        self.sell_buildings_to_bank(player_id);
+        let mut property_ids = Vec::new();
+        for property in self.properties.iter_mut().filter(|p| p.owner_id.as_deref() == Some(player_id)) {
+            property.owner_id = None;
+            property.is_mortgaged = false;
+            property_ids.push(property.id);
         }
+        let policy = match self.rules.estate_policy {
+            EstatePolicy::Auction => {
+                self.pending_auctions.extend(property_ids.iter().copied());
+                EstatePolicy::Auction
+            }
+            _ => EstatePolicy::ReturnToBank,
+        };
+        events.push(ServerMessage::EstateSettled {
+            player_id: player_id.to_string(),
+            policy,
+            property_ids,
+            bot_id: None,
+        });
 
         // Remove player from list
         if let Some(idx) = self.players.iter().position(|p| p.id == player_id) {
             self.players.remove(idx);
             
             // Adjust current turn if necessary
+            if idx < self.current_turn {
+                self.current_turn -= 1;
+            }
             if self.players.is_empty() {
                 self.current_turn = 0;
             } else {
                 self.current_turn = self.current_turn % self.players.len();
             }
         }
+
+        events.extend(self.check_victory());
+        if was_current {
+            self.rent_paid = false;
+            self.offer_declined = true;
+            self.rent_override = None;
+            match self.phase {
+                // Whatever is still being settled hands over to the next player
+                GamePhase::Auction => {
+                    if let Some(auction) = &mut self.auction {
+                        auction.return_phase = GamePhase::Rolling;
+                    }
+                }
+                GamePhase::DebtResolution => {
+                    for debt in self.debts.iter_mut() {
+                        debt.return_phase = GamePhase::Rolling;
+                    }
+                }
+                GamePhase::GameOver => {}
+                _ => {
+                    self.phase = GamePhase::Rolling;
+                    if let Some(player) = self.players.get_mut(self.current_turn) {
+                        player.doubles_count = 0;
+                    }
+                    events.push(ServerMessage::TurnEnded { state: self.clone() });
+                }
+            }
+        }
+        events
+    }
+
+    /// Gives a departing player's seat, with everything they had, to a bot
+    /// joining as `bot_id`. Their open trades are cancelled.
+    pub fn hand_seat_to_bot(&mut self, player_id: &str, bot_id: &str, bot_name: &str) -> Result<Vec<ServerMessage>, String> {
+        let player = self.players.iter_mut().find(|p| p.id == player_id).ok_or("Player not found")?;
+        player.id = bot_id.to_string();
+        player.name = bot_name.to_string();
+
+        let mut property_ids = Vec::new();
+        for property in self.properties.iter_mut().filter(|p| p.owner_id.as_deref() == Some(player_id)) {
+            property.owner_id = Some(bot_id.to_string());
+            property_ids.push(property.id);
+        }
+        for debt in self.debts.iter_mut() {
+            if debt.debtor_id == player_id {
+                debt.debtor_id = bot_id.to_string();
+            }
+            if debt.creditor.player_id() == Some(player_id) {
+                debt.creditor = Creditor::Player(bot_id.to_string());
+            }
+        }
+        if let Some(auction) = &mut self.auction {
+            for bidder in auction.bidders.iter_mut().chain(auction.highest_bidder.as_mut()) {
+                if bidder == player_id {
+                    *bidder = bot_id.to_string();
+                }
+            }
+        }
+        for decision in self.mortgage_decisions.iter_mut().filter(|d| d.player_id == player_id) {
+            decision.player_id = bot_id.to_string();
+        }
+
+        let mut events = self.cancel_trades_of(player_id);
+        events.push(ServerMessage::EstateSettled {
+            player_id: player_id.to_string(),
+            policy: EstatePolicy::HandToBot,
+            property_ids,
+            bot_id: Some(bot_id.to_string()),
+        });
+        Ok(events)
     }
 
     pub fn pay_bail(&mut self, player_index: usize) -> Result<Vec<ServerMessage>, String> {
+        let bail_amount = self.rules.bail_amount;
         if let Some(player) = self.players.get_mut(player_index) {
             if !player.is_in_jail {
                 return Err("Player is not in jail".to_string());
             }
-            if player.money < 50_000 {
+            if player.money < bail_amount {
                 return Err("Not enough money".to_string());
             }
             
-            player.money -= 50_000;
+            player.money -= bail_amount;
             player.is_in_jail = false;
             player.jail_turns = 0;
             
@@ -841,7 +1067,7 @@ impl GameState {
             }
             
             // Check if player has a Get Out of Jail Free card
-            if let Some(card_idx) = player.held_cards.iter().position(|c| c.effect_type == "get_out_of_jail") {
+            if let Some(card_idx) = player.held_cards.iter().position(|c| c.effect == CardEffect::GetOutOfJail) {
                 // Remove the card from player's hand
                 let _card = player.held_cards.remove(card_idx);
                 
@@ -864,7 +1090,129 @@ impl GameState {
         }
     }
 
-    pub fn handle_propose_trade(&mut self, initiator_id: String, target_player_id: String, offer: crate::game::trade::TradeOffer, request: crate::game::trade::TradeOffer) -> Result<Vec<ServerMessage>, String> {
+    pub fn handle_propose_trade(&mut self, initiator_id: String, target_player_id: String, offer: crate::game::trade::TradeOffer, request: crate::game::trade::TradeOffer, now: u64) -> Result<Vec<ServerMessage>, String> {
+        self.propose_trade(initiator_id, target_player_id, offer, request, None, now)
+    }
+
+    /// Replaces a trade offered to the player with their own proposal back to
+    /// its initiator
+    pub fn handle_counter_trade(&mut self, trade_id: String, player_id: String, offer: crate::game::trade::TradeOffer, request: crate::game::trade::TradeOffer, now: u64) -> Result<Vec<ServerMessage>, String> {
+        let original = self.active_trades.get(&trade_id).ok_or("Trade not found")?;
+        if original.target_player_id != player_id {
+            return Err("You are not the target of this trade".to_string());
+        }
+        let initiator_id = original.initiator_id.clone();
+
+        let mut events = self.propose_trade(player_id, initiator_id, offer, request, Some(trade_id.clone()), now)?;
+        self.active_trades.remove(&trade_id);
+        events.insert(0, ServerMessage::TradeRejected { trade_id });
+        Ok(events)
+    }
+
+    /// Properties only change hands once every building in their group is
+    /// sold, and not while a mortgage decision on them is open
+    fn check_tradable(&self, property_id: usize) -> Result<(), String> {
+        let info = self.board.property(property_id).ok_or("Property not found")?;
+        let built = self.properties.iter()
+            .any(|p| p.houses > 0 && self.board.property(p.id).is_some_and(|other| other.group == info.group));
+        if built {
+            return Err(format!("Sell the buildings in the group of property {} before trading it", property_id));
+        }
+        if self.mortgage_decisions.iter().any(|d| d.property_id == property_id) {
+            return Err(format!("Property {} is waiting on a mortgage decision", property_id));
+        }
+        Ok(())
+    }
+
+    /// Asks the new owner of a mortgaged property to lift or keep the mortgage
+    fn require_mortgage_decision(&mut self, player_id: String, property_id: usize) -> ServerMessage {
+        let mortgage_value = self.board.property(property_id).map(|info| info.mortgage_value).unwrap_or(0);
+        let decision = crate::game::trade::MortgageDecision {
+            player_id,
+            property_id,
+            fee: mortgage_value / 10,
+            unmortgage_cost: mortgage_value + mortgage_value / 10,
+        };
+        self.mortgage_decisions.push(decision.clone());
+        ServerMessage::MortgageDecisionRequired { decision }
+    }
+
+    /// Settles a mortgaged property received in a trade: lift the mortgage
+    /// now, or pay the 10% fee and keep it mortgaged
+    pub fn handle_resolve_mortgage(&mut self, player_id: &str, property_id: usize, unmortgage: bool) -> Result<Vec<ServerMessage>, String> {
+        let decision = self.mortgage_decisions.iter()
+            .find(|d| d.player_id == player_id && d.property_id == property_id)
+            .cloned()
+            .ok_or("No mortgage decision pending for this property")?;
+
+        if unmortgage {
+            // Lifting the mortgage closes the decision
+            return self.handle_unmortgage_property(player_id.to_string(), property_id);
+        }
+        self.mortgage_decisions.retain(|d| d != &decision);
+        let player_idx = self.players.iter().position(|p| p.id == player_id).ok_or("Player not found")?;
+        let mut events = self.charge(player_idx, decision.fee, Creditor::Bank);
+        events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+        Ok(events)
+    }
+
+    /// Rolling and ending the turn wait until the player has settled the
+    /// mortgaged properties they received
+    fn check_mortgage_decisions(&self, player_id: &str) -> Result<(), String> {
+        if self.mortgage_decisions.iter().any(|d| d.player_id == player_id) {
+            return Err("Decide on the mortgaged properties you received first".to_string());
+        }
+        Ok(())
+    }
+
+    /// The game waits while estates of a bankrupt player are being auctioned
+    fn check_no_bank_auctions(&self) -> Result<(), String> {
+        if !self.pending_auctions.is_empty() {
+            return Err("Waiting for the bank to auction a bankrupt player's estates".to_string());
+        }
+        Ok(())
+    }
+
+    /// Moves `count` Get-Out-of-Jail cards from one player's hand to another's
+    fn give_jail_cards(players: &mut [PlayerState], from: usize, to: usize, count: u8) {
+        for _ in 0..count {
+            let Some(idx) = players[from].held_cards.iter().position(|c| c.effect == CardEffect::GetOutOfJail) else {
+                return;
+            };
+            let card = players[from].held_cards.remove(idx);
+            players[to].held_cards.push(card);
+        }
+    }
+
+    /// Cancels every open trade the player is a party to
+    fn cancel_trades_of(&mut self, player_id: &str) -> Vec<ServerMessage> {
+        let mut cancelled: Vec<String> = self.active_trades.values()
+            .filter(|t| t.involves(player_id))
+            .map(|t| t.id.clone())
+            .collect();
+        cancelled.sort();
+
+        cancelled.into_iter().map(|trade_id| {
+            self.active_trades.remove(&trade_id);
+            ServerMessage::TradeCancelled { trade_id }
+        }).collect()
+    }
+
+    /// Cancels the trade proposals left unanswered past their deadline
+    pub fn expire_trades(&mut self, now: u64) -> Vec<ServerMessage> {
+        let mut expired: Vec<String> = self.active_trades.values()
+            .filter(|t| t.expires_at.is_some_and(|at| now >= at))
+            .map(|t| t.id.clone())
+            .collect();
+        expired.sort();
+
+        expired.into_iter().map(|trade_id| {
+            self.active_trades.remove(&trade_id);
+            ServerMessage::TradeCancelled { trade_id }
+        }).collect()
+    }
+
+    fn propose_trade(&mut self, initiator_id: String, target_player_id: String, offer: crate::game::trade::TradeOffer, request: crate::game::trade::TradeOffer, counter_to: Option<String>, now: u64) -> Result<Vec<ServerMessage>, String> {
         // Validate players exist
         if !self.players.iter().any(|p| p.id == initiator_id) {
             return Err("Initiator not found".to_string());
@@ -873,6 +1221,14 @@ impl GameState {
             return Err("Target player not found".to_string());
         }
 
+        // Counter-offers count against the limit like any other proposal
+        let open = self.active_trades.values()
+            .filter(|t| t.initiator_id == initiator_id)
+            .count();
+        if open >= self.rules.max_open_trades {
+            return Err(format!("You already have {} open trade proposals", open));
+        }
+
         // Validate ownership of offered items
         let initiator = self.players.iter().find(|p| p.id == initiator_id).unwrap();
         if initiator.money < offer.money {
@@ -883,24 +1239,33 @@ impl GameState {
                 if prop.owner_id.as_ref() != Some(&initiator_id) {
                     return Err(format!("You do not own property {}", prop_id));
                 }
+                self.check_tradable(*prop_id)?;
             } else {
                 return Err(format!("Property {} not found", prop_id));
             }
         }
 
+        if initiator.jail_card_count() < offer.jail_cards {
+            return Err("Not enough jail cards to offer".to_string());
+        }
+
         // Validate target owns requested properties
-        let _target = self.players.iter().find(|p| p.id == target_player_id).unwrap();
+        let target = self.players.iter().find(|p| p.id == target_player_id).unwrap();
+        if target.jail_card_count() < request.jail_cards {
+            return Err("Target does not have enough jail cards".to_string());
+        }
         for prop_id in &request.property_ids {
             if let Some(prop) = self.properties.iter().find(|p| p.id == *prop_id) {
                 if prop.owner_id.as_ref() != Some(&target_player_id) {
                     return Err(format!("Target does not own property {}", prop_id));
                 }
+                self.check_tradable(*prop_id)?;
             } else {
                 return Err(format!("Property {} not found", prop_id));
             }
         }
 
-        let trade_id = uuid::Uuid::new_v4().to_string();
+        let trade_id = self.rng.next_id();
         let proposal = crate::game::trade::TradeProposal {
             id: trade_id.clone(),
             initiator_id: initiator_id.clone(),
@@ -908,11 +1273,17 @@ impl GameState {
             offer,
             request,
             status: crate::game::trade::TradeStatus::Pending,
+            counter_to,
+            expires_at: Some(now + self.rules.trade_expiry_secs).filter(|_| self.rules.trade_expiry_secs > 0),
         };
 
         self.active_trades.insert(trade_id.clone(), proposal.clone());
 
-        Ok(vec![ServerMessage::TradeProposed { proposal }])
+        // The offer itself only goes to the two parties
+        Ok(vec![
+            ServerMessage::TradeProposed { proposal },
+            ServerMessage::TradeNegotiating { trade_id, initiator_id, target_player_id },
+        ])
     }
 
     pub fn handle_accept_trade(&mut self, trade_id: String, player_id: String) -> Result<Vec<ServerMessage>, String> {
@@ -933,18 +1304,28 @@ impl GameState {
                 return Err("You do not have enough money".to_string());
             }
 
+            // Check jail cards
+            if self.players[initiator_idx].jail_card_count() < proposal.offer.jail_cards {
+                return Err("Initiator no longer has the offered jail cards".to_string());
+            }
+            if self.players[target_idx].jail_card_count() < proposal.request.jail_cards {
+                return Err("You no longer have the requested jail cards".to_string());
+            }
+
             // Check properties
             for prop_id in &proposal.offer.property_ids {
                 let prop = self.properties.iter().find(|p| p.id == *prop_id).ok_or("Property not found")?;
                 if prop.owner_id.as_ref() != Some(&proposal.initiator_id) {
                     return Err("Initiator no longer owns offered property".to_string());
                 }
+                self.check_tradable(*prop_id)?;
             }
             for prop_id in &proposal.request.property_ids {
                 let prop = self.properties.iter().find(|p| p.id == *prop_id).ok_or("Property not found")?;
                 if prop.owner_id.as_ref() != Some(&proposal.target_player_id) {
                     return Err("You no longer own requested property".to_string());
                 }
+                self.check_tradable(*prop_id)?;
             }
 
             // Execute Trade
@@ -955,12 +1336,17 @@ impl GameState {
             self.players[target_idx].money -= proposal.request.money;
             self.players[initiator_idx].money += proposal.request.money;
 
-            // 2. Properties
+            // 2. Jail cards
+            Self::give_jail_cards(&mut self.players, initiator_idx, target_idx, proposal.offer.jail_cards);
+            Self::give_jail_cards(&mut self.players, target_idx, initiator_idx, proposal.request.jail_cards);
+
+            // 3. Properties
             // We need to collect property indices first to avoid borrow checker issues if we iterate self.properties mutably
             // Actually, we can just iterate and match IDs.
             let offer_props = proposal.offer.property_ids.clone();
             let request_props = proposal.request.property_ids.clone();
 
+            let mut received_mortgages = Vec::new();
             for prop in &mut self.properties {
                 if offer_props.contains(&prop.id) {
                     prop.owner_id = Some(proposal.target_player_id.clone());
@@ -968,15 +1354,23 @@ impl GameState {
                 if request_props.contains(&prop.id) {
                     prop.owner_id = Some(proposal.initiator_id.clone());
                 }
+                if prop.is_mortgaged && (offer_props.contains(&prop.id) || request_props.contains(&prop.id)) {
+                    received_mortgages.push((prop.owner_id.clone().unwrap_or_default(), prop.id));
+                }
             }
 
-            // Remove trade
+            // Remove trade, and publish it now that it is done
+            let mut proposal = proposal.clone();
+            proposal.status = crate::game::trade::TradeStatus::Accepted;
             self.active_trades.remove(&trade_id);
+            let mut events = vec![ServerMessage::TradeAccepted { trade_id, proposal }];
 
-            Ok(vec![
-                ServerMessage::TradeAccepted { trade_id },
-                ServerMessage::GameStateUpdate { state: self.clone() }
-            ])
+            // 4. Mortgaged properties wait on their new owner's decision
+            for (player_id, property_id) in received_mortgages {
+                events.push(self.require_mortgage_decision(player_id, property_id));
+            }
+            events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+            Ok(events)
         } else {
             Err("Trade not found".to_string())
         }
@@ -1005,54 +1399,291 @@ impl GameState {
             Err("Trade not found".to_string())
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::game::state::{GameState, GamePhase};
 
-    fn create_test_game() -> GameState {
-        let mut game = GameState::new();
-        game.players.push(crate::game::state::PlayerState {
-            id: "player1".to_string(),
-            name: "Player 1".to_string(),
-            money: 1500000,
-            position: 0,
-            color: "red".to_string(),
-            is_in_jail: false,
-            jail_turns: 0,
-            doubles_count: 0,
-            held_cards: Vec::new(),
-        });
-        game.players.push(crate::game::state::PlayerState {
-            id: "player2".to_string(),
-            name: "Player 2".to_string(),
-            money: 1500000,
-            position: 0,
-            color: "blue".to_string(),
-            is_in_jail: false,
-            jail_turns: 0,
-            doubles_count: 0,
-            held_cards: Vec::new(),
-        });
-        game.current_turn = 0;
-        game.phase = GamePhase::Rolling;
-        game
+    /// Player the game is waiting on: the first debtor during DebtResolution,
+    /// otherwise the player whose turn it is
+    pub fn acting_player_id(&self) -> Option<&str> {
+        match self.phase {
+            GamePhase::DebtResolution => self.debts.first().map(|d| d.debtor_id.as_str()),
+            _ => self.players.get(self.current_turn).map(|p| p.id.as_str()),
+        }
     }
 
-    #[test]
-    fn test_handle_roll_success() {
-        let mut game = create_test_game();
-        // Ensure it's player 1's turn and Rolling phase
-        assert_eq!(game.current_turn, 0);
-        assert_eq!(game.phase, GamePhase::Rolling);
+    /// Plays the next step for a player whose turn timer ran out: roll, decline
+    /// the purchase (sending the tile to auction), pay rent, then end the turn.
+    /// Open mortgage decisions are settled by keeping the mortgage.
+    /// A debtor pays the debt if they can and goes bankrupt otherwise.
+    pub fn handle_turn_timeout(&mut self, now: u64) -> Vec<ServerMessage> {
+        let Some(player_id) = self.acting_player_id().map(|id| id.to_string()) else {
+            return Vec::new();
+        };
+        tracing::info!("[FSM] handle_turn_timeout: player_id={}, phase={:?}", player_id, self.phase);
 
-        let result = game.handle_roll("player1");
-        assert!(result.is_ok());
-    }
+        let mut events = vec![ServerMessage::TurnTimedOut { player_id: player_id.clone() }];
+        // Mortgages received in trades are kept, paying the fee
+        let pending: Vec<usize> = self.mortgage_decisions.iter()
+            .filter(|d| d.player_id == player_id)
+            .map(|d| d.property_id)
+            .collect();
+        for property_id in pending {
+            events.extend(self.handle_resolve_mortgage(&player_id, property_id, false).unwrap_or_default());
+        }
+        let result = match self.phase {
+            GamePhase::Rolling if self.offer_pending() => self.handle_decline_property(&player_id, now),
+            GamePhase::Rolling => self.handle_roll(&player_id).map(|(dice, roll_events)| {
+                let mut events = vec![ServerMessage::DiceRolled { dice, state: self.clone() }];
+                events.extend(roll_events);
+                events
+            }),
+            GamePhase::EndTurn => {
+                let position = self.players[self.current_turn].position;
+                let owner = self.properties.iter().find(|p| p.id == position).map(|p| p.owner_id.clone());
+                match owner {
+                    Some(None) if !self.offer_declined => self.handle_decline_property(&player_id, now),
+                    Some(Some(owner_id)) if owner_id != player_id && !self.rent_paid => {
+                        crate::game::actions::handle_pay_rent(self, &player_id)
+                    },
+                    _ => self.next_turn(&player_id).map(|_| vec![ServerMessage::TurnEnded { state: self.clone() }]),
+                }
+            },
+            GamePhase::DebtResolution => self.handle_pay_debt(&player_id)
+                .or_else(|_| self.handle_declare_bankruptcy(&player_id)),
+            _ => Ok(Vec::new()),
+        };
 
-    #[test]
-    fn test_handle_roll_wrong_turn() {
+        match result {
+            Ok(action_events) => events.extend(action_events),
+            Err(e) => tracing::warn!("[FSM] handle_turn_timeout: FAIL - player_id={}, error={}", player_id, e),
+        }
+        events.push(ServerMessage::GameStateUpdate { state: self.clone() });
+        events
+    }
+
+    pub fn handle_decline_property(&mut self, player_id: &str, now: u64) -> Result<Vec<ServerMessage>, String> {
+        tracing::info!(
+            "[FSM] handle_decline_property: START - player_id={}, current_turn={}, phase={:?}",
+            player_id, self.current_turn, self.phase
+        );
+
+        self.check_turn(player_id)?;
+        self.check_offer_phase()?;
+
+        let position = self.players[self.current_turn].position;
+        self.board.property(position).ok_or("Not a property")?;
+        let property = self.properties.iter().find(|p| p.id == position).ok_or("Property state not found")?;
+        if property.owner_id.is_some() {
+            return Err("Property already owned".to_string());
+        }
+        if self.offer_declined {
+            return Err("Property was already auctioned".to_string());
+        }
+
+        self.offer_declined = true;
+        Ok(self.start_auction(position, now))
+    }
+
+    /// Whether the acting player stands on an unowned property they landed on
+    /// this turn and have neither bought nor declined
+    pub fn offer_pending(&self) -> bool {
+        let Some(player) = self.players.get(self.current_turn) else {
+            return false;
+        };
+        !self.offer_declined
+            && self.board.property(player.position).is_some()
+            && self.properties.iter().any(|p| p.id == player.position && p.owner_id.is_none())
+    }
+
+    /// The current tile can be bought or declined once the roll has resolved,
+    /// and also before rolling again after doubles
+    pub fn check_offer_phase(&self) -> Result<(), String> {
+        if self.phase == GamePhase::Rolling && self.offer_pending() {
+            return Ok(());
+        }
+        self.check_phase(GamePhase::EndTurn)
+    }
+
+    /// Whether estates returned by a bankruptcy are waiting to be auctioned
+    /// and the game is free to run the auction
+    pub fn bank_auction_due(&self) -> bool {
+        !self.pending_auctions.is_empty() && matches!(self.phase, GamePhase::Rolling | GamePhase::EndTurn)
+    }
+
+    pub fn auction_bank_estate(&mut self, now: u64) -> Result<Vec<ServerMessage>, String> {
+        if !self.bank_auction_due() {
+            return Err("No bank auction is due".to_string());
+        }
+        let property_id = self.pending_auctions.remove(0);
+        Ok(self.start_auction(property_id, now))
+    }
+
+    pub fn start_auction(&mut self, property_id: usize, now: u64) -> Vec<ServerMessage> {
+        let old_phase = self.phase.clone();
+        let auction = crate::game::auction::Auction {
+            id: self.rng.next_id(),
+            property_id,
+            highest_bid: 0,
+            highest_bidder: None,
+            bidders: self.players.iter().map(|p| p.id.clone()).collect(),
+            ends_at: now + crate::game::auction::AUCTION_DURATION_SECS,
+            return_phase: old_phase.clone(),
+        };
+
+        self.phase = GamePhase::Auction;
+        self.auction = Some(auction.clone());
+
+        tracing::info!(
+            "[FSM] start_auction: TRANSITION - property_id={}, phase: {:?} -> {:?}",
+            property_id, old_phase, self.phase
+        );
+
+        vec![
+            ServerMessage::AuctionStarted { auction },
+            ServerMessage::GameStateUpdate { state: self.clone() },
+        ]
+    }
+
+    pub fn handle_place_bid(&mut self, player_id: &str, amount: i32, now: u64) -> Result<Vec<ServerMessage>, String> {
+        self.check_phase(GamePhase::Auction)?;
+
+        let money = self.players.iter().find(|p| p.id == player_id).map(|p| p.money).ok_or("Player not found")?;
+        let auction = self.auction.as_mut().ok_or("No auction in progress")?;
+
+        if !auction.bidders.iter().any(|id| id == player_id) {
+            return Err("You have already passed on this auction".to_string());
+        }
+        if amount < auction.min_next_bid() {
+            return Err(format!("Bid must be at least {}", auction.min_next_bid()));
+        }
+        if money < amount {
+            return Err("Insufficient funds".to_string());
+        }
+
+        auction.highest_bid = amount;
+        auction.highest_bidder = Some(player_id.to_string());
+        auction.ends_at = now + crate::game::auction::AUCTION_DURATION_SECS;
+
+        tracing::info!(
+            "[FSM] handle_place_bid: SUCCESS - player_id={}, property_id={}, amount={}",
+            player_id, auction.property_id, amount
+        );
+
+        let mut events = vec![ServerMessage::AuctionBidPlaced {
+            player_id: player_id.to_string(),
+            amount,
+            ends_at: auction.ends_at,
+        }];
+        if auction.is_decided() {
+            events.extend(self.finish_auction());
+        }
+        Ok(events)
+    }
+
+    pub fn handle_pass_auction(&mut self, player_id: &str) -> Result<Vec<ServerMessage>, String> {
+        self.check_phase(GamePhase::Auction)?;
+
+        let auction = self.auction.as_mut().ok_or("No auction in progress")?;
+        if auction.highest_bidder.as_deref() == Some(player_id) {
+            return Err("The highest bidder cannot pass".to_string());
+        }
+        let idx = auction.bidders.iter().position(|id| id == player_id)
+            .ok_or("You have already passed on this auction")?;
+        auction.bidders.remove(idx);
+
+        let mut events = vec![ServerMessage::AuctionPassed { player_id: player_id.to_string() }];
+        if auction.is_decided() {
+            events.extend(self.finish_auction());
+        }
+        Ok(events)
+    }
+
+    /// Closes the running auction if its countdown has run out
+    pub fn resolve_expired_auction(&mut self, now: u64) -> Vec<ServerMessage> {
+        match &self.auction {
+            Some(auction) if now >= auction.ends_at => self.finish_auction(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn finish_auction(&mut self) -> Vec<ServerMessage> {
+        let auction = match self.auction.take() {
+            Some(auction) => auction,
+            None => return Vec::new(),
+        };
+
+        let mut winner_id = None;
+        if let Some(bidder_id) = &auction.highest_bidder {
+            if let Some(player) = self.players.iter_mut().find(|p| &p.id == bidder_id) {
+                if player.money >= auction.highest_bid {
+                    player.money -= auction.highest_bid;
+                    if let Some(property) = self.properties.iter_mut().find(|p| p.id == auction.property_id) {
+                        property.owner_id = Some(bidder_id.clone());
+                    }
+                    winner_id = Some(bidder_id.clone());
+                }
+            }
+        }
+
+        let old_phase = self.phase.clone();
+        self.phase = auction.return_phase;
+
+        tracing::info!(
+            "[FSM] finish_auction: TRANSITION - property_id={}, winner={:?}, amount={}, phase: {:?} -> {:?}",
+            auction.property_id, winner_id, auction.highest_bid, old_phase, self.phase
+        );
+
+        vec![
+            ServerMessage::AuctionEnded {
+                property_id: auction.property_id,
+                amount: if winner_id.is_some() { auction.highest_bid } else { 0 },
+                winner_id,
+            },
+            ServerMessage::GameStateUpdate { state: self.clone() },
+        ]
+    }
+
+    /// Drops a leaving player from the running auction, closing it if that decides it
+    fn remove_auction_bidder(&mut self, player_id: &str) -> Vec<ServerMessage> {
+        if let Some(auction) = &mut self.auction {
+            auction.bidders.retain(|id| id != player_id);
+            if auction.highest_bidder.as_deref() == Some(player_id) {
+                auction.highest_bidder = None;
+                auction.highest_bid = 0;
+            }
+            if auction.is_decided() {
+                return self.finish_auction();
+            }
+        }
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::state::{GameState, GamePhase};
+    use crate::game::debt::Creditor;
+
+    fn create_test_game() -> GameState {
+        let mut game = GameState::new();
+        game.players.push(crate::game::state::PlayerState::new("player1", "Player 1", 1500000));
+        game.players.push(crate::game::state::PlayerState::new("player2", "Player 2", 1500000));
+        game.current_turn = 0;
+        game.phase = GamePhase::Rolling;
+        game
+    }
+
+    #[test]
+    fn test_handle_roll_success() {
+        let mut game = create_test_game();
+        // Ensure it's player 1's turn and Rolling phase
+        assert_eq!(game.current_turn, 0);
+        assert_eq!(game.phase, GamePhase::Rolling);
+
+        let result = game.handle_roll("player1");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_handle_roll_wrong_turn() {
         let mut game = create_test_game();
         let result = game.handle_roll("player2");
         assert!(result.is_err());
@@ -1104,10 +1735,10 @@ mod tests {
         let mut game = create_test_game();
         
         // 1. Propose Trade
-        let offer = crate::game::trade::TradeOffer { money: 100, property_ids: vec![] };
-        let request = crate::game::trade::TradeOffer { money: 0, property_ids: vec![] };
+        let offer = crate::game::trade::TradeOffer { money: 100, property_ids: vec![], jail_cards: 0 };
+        let request = crate::game::trade::TradeOffer { money: 0, property_ids: vec![], jail_cards: 0 };
         
-        let result = game.handle_propose_trade("player1".to_string(), "player2".to_string(), offer.clone(), request.clone());
+        let result = game.handle_propose_trade("player1".to_string(), "player2".to_string(), offer.clone(), request.clone(), 1000);
         assert!(result.is_ok());
         
         let trade_id = game.active_trades.keys().next().unwrap().clone();
@@ -1126,4 +1757,711 @@ mod tests {
         // Verify trade removed
         assert!(game.active_trades.is_empty());
     }
+
+    #[test]
+    fn test_trade_jail_cards() {
+        let mut game = create_test_game();
+        game.players[0].held_cards.push(crate::game::state::Card {
+            id: 0,
+            title: "Kartu Bebas Skorsing".to_string(),
+            description: String::new(),
+            effect: crate::game::state::CardEffect::GetOutOfJail,
+            is_chance: false,
+        });
+        let offer = |jail_cards| crate::game::trade::TradeOffer { money: 0, property_ids: vec![], jail_cards };
+        let payment = crate::game::trade::TradeOffer { money: 50_000, property_ids: vec![], jail_cards: 0 };
+
+        assert!(game.handle_propose_trade("player1".to_string(), "player2".to_string(), offer(2), payment.clone(), 1000).is_err());
+        assert!(game.handle_propose_trade("player2".to_string(), "player1".to_string(), payment.clone(), offer(2), 1000).is_err());
+        game.handle_propose_trade("player1".to_string(), "player2".to_string(), offer(1), payment, 1000).unwrap();
+
+        let trade_id = game.active_trades.keys().next().unwrap().clone();
+        game.handle_accept_trade(trade_id, "player2".to_string()).unwrap();
+        assert_eq!(game.players[0].jail_card_count(), 0);
+        assert_eq!(game.players[1].jail_card_count(), 1);
+        assert_eq!(game.players[0].money, 1500000 + 50_000);
+    }
+
+    #[test]
+    fn test_trade_restrictions_on_buildings_and_mortgages() {
+        let mut game = create_test_game();
+        let street = |game: &GameState, index| game.board.properties()
+            .filter(|(_, info)| info.group == crate::game::board::PropertyGroup::Brown)
+            .map(|(tile, _)| tile.id)
+            .nth(index)
+            .unwrap();
+        let (built, empty) = (street(&game, 0), street(&game, 1));
+        for property in game.properties.iter_mut().filter(|p| p.id == built || p.id == empty) {
+            property.owner_id = Some("player1".to_string());
+        }
+        game.properties.iter_mut().find(|p| p.id == built).unwrap().houses = 1;
+        let props = |property_ids| crate::game::trade::TradeOffer { money: 0, property_ids, jail_cards: 0 };
+
+        // Nothing in a group with buildings can be traded
+        assert!(game.handle_propose_trade("player1".to_string(), "player2".to_string(), props(vec![empty]), props(vec![]), 1000).is_err());
+
+        // A mortgaged property waits on its receiver's decision
+        game.properties.iter_mut().find(|p| p.id == built).unwrap().houses = 0;
+        game.properties.iter_mut().find(|p| p.id == empty).unwrap().is_mortgaged = true;
+        game.handle_propose_trade("player1".to_string(), "player2".to_string(), props(vec![empty]), props(vec![]), 1000).unwrap();
+        let trade_id = game.active_trades.keys().next().unwrap().clone();
+        let events = game.handle_accept_trade(trade_id, "player2".to_string()).unwrap();
+        assert!(events.iter().any(|e| matches!(e, crate::ws::messages::ServerMessage::MortgageDecisionRequired { .. })));
+        let decision = game.mortgage_decisions[0].clone();
+        assert_eq!(decision.player_id, "player2");
+
+        game.current_turn = 1;
+        assert!(game.handle_roll("player2").is_err());
+        assert!(game.handle_resolve_mortgage("player1", empty, false).is_err());
+        game.handle_resolve_mortgage("player2", empty, false).unwrap();
+        assert_eq!(game.players[1].money, 1500000 - decision.fee);
+        assert!(game.properties.iter().find(|p| p.id == empty).unwrap().is_mortgaged);
+        assert!(game.mortgage_decisions.is_empty());
+        assert!(game.handle_roll("player2").is_ok());
+    }
+
+    #[test]
+    fn test_trade_counter_offer_limit_and_expiry() {
+        let mut game = create_test_game();
+        game.rules.max_open_trades = 2;
+        let cash = |money| crate::game::trade::TradeOffer { money, property_ids: vec![], jail_cards: 0 };
+
+        game.handle_propose_trade("player1".to_string(), "player2".to_string(), cash(100), cash(0), 1000).unwrap();
+        game.handle_propose_trade("player1".to_string(), "player2".to_string(), cash(200), cash(0), 1000).unwrap();
+        assert!(game.handle_propose_trade("player1".to_string(), "player2".to_string(), cash(300), cash(0), 1000).is_err());
+
+        // Countering replaces the original with a linked proposal the other way
+        let original = game.active_trades.values().find(|t| t.offer.money == 100).unwrap().id.clone();
+        assert!(game.handle_counter_trade(original.clone(), "player1".to_string(), cash(0), cash(0), 1010).is_err());
+        let events = game.handle_counter_trade(original.clone(), "player2".to_string(), cash(150), cash(0), 1010).unwrap();
+        assert!(matches!(&events[0], crate::ws::messages::ServerMessage::TradeRejected { trade_id } if *trade_id == original));
+        let counter = game.active_trades.values().find(|t| t.counter_to.as_ref() == Some(&original)).unwrap();
+        assert_eq!(counter.initiator_id, "player2");
+        assert!(!game.active_trades.contains_key(&original));
+
+        // A counter-offer counts towards its sender's limit
+        game.handle_propose_trade("player2".to_string(), "player1".to_string(), cash(50), cash(0), 1010).unwrap();
+        let second = game.active_trades.values().find(|t| t.offer.money == 200).unwrap().id.clone();
+        assert!(game.handle_counter_trade(second.clone(), "player2".to_string(), cash(250), cash(0), 1010).is_err());
+        assert!(game.active_trades.contains_key(&second));
+
+        // Unanswered proposals are cancelled once they expire
+        let expiry = game.rules.trade_expiry_secs;
+        assert!(game.expire_trades(1000 + expiry - 1).is_empty());
+        assert_eq!(game.expire_trades(1000 + expiry).len(), 1);
+        assert_eq!(game.expire_trades(1010 + expiry).len(), 2);
+        assert!(game.active_trades.is_empty());
+    }
+
+    #[test]
+    fn test_movement_uses_board_size() {
+        let board = crate::game::board::Board::from_json(r#"{"id": "mini", "name": "Mini", "tiles": [
+            {"id": 0, "name": "GO", "type": "Go"},
+            {"id": 1, "name": "Jail", "type": "Jail"},
+            {"id": 2, "name": "Parkir", "type": "FreeParking"},
+            {"id": 3, "name": "Pajak", "type": "Tax", "amount": 10000}
+        ]}"#).unwrap();
+        let mut game = GameState::with_board(board, &crate::game::cards::DeckSet::default(), crate::game::rules::HouseRules::default(), crate::game::rng::GameRng::new(7));
+        game.players = create_test_game().players;
+
+        game.move_player(0, 7);
+        assert_eq!(game.players[0].position, 3);
+        assert_eq!(game.players[0].money, 1500000 + 200_000);
+
+        game.handle_landing(0);
+        assert_eq!(game.players[0].money, 1500000 + 200_000 - 10_000);
+    }
+
+    #[test]
+    fn test_typed_card_effects() {
+        use crate::game::state::{Card, CardEffect};
+        let mut game = create_test_game();
+        game.properties.iter_mut().find(|p| p.id == 1).unwrap().owner_id = Some("player1".to_string());
+        game.properties.iter_mut().find(|p| p.id == 1).unwrap().houses = 2;
+        game.properties.iter_mut().find(|p| p.id == 3).unwrap().owner_id = Some("player1".to_string());
+        game.properties.iter_mut().find(|p| p.id == 3).unwrap().houses = 5;
+
+        let repair = Card {
+            id: 99,
+            title: "Any title".to_string(),
+            description: String::new(),
+            effect: CardEffect::Repair { per_house: 10_000, per_hotel: 50_000 },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &repair);
+        assert_eq!(game.players[0].money, 1500000 - 70_000);
+
+        let pay_each = Card {
+            id: 100,
+            title: "Traktir".to_string(),
+            description: String::new(),
+            effect: CardEffect::PayEachPlayer { amount: 20_000 },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &pay_each);
+        assert_eq!(game.players[0].money, 1500000 - 90_000);
+        assert_eq!(game.players[1].money, 1500000 + 20_000);
+    }
+
+    #[test]
+    fn test_card_movement_resolves_landing() {
+        use crate::game::state::{Card, CardEffect};
+        let mut game = create_test_game();
+
+        // Back 3 from SIAK-NG (7) lands on Bayar UKT (4)
+        game.players[0].position = 7;
+        let back = Card {
+            id: 9,
+            title: "SIAK Error".to_string(),
+            description: String::new(),
+            effect: CardEffect::Back { steps: 3 },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &back);
+        assert_eq!(game.players[0].position, 4);
+        assert_eq!(game.players[0].money, 1500000 - 200_000);
+
+        // Advancing onto Sanksi Akademik sends to jail
+        let advance = Card {
+            id: 1,
+            title: "Ke Sanksi".to_string(),
+            description: String::new(),
+            effect: CardEffect::Advance { target: 30 },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &advance);
+        assert!(game.players[0].is_in_jail);
+        assert_eq!(game.players[0].position, 10);
+    }
+
+    #[test]
+    fn test_nearest_railroad_card_doubles_rent() {
+        use crate::game::state::{Card, CardEffect};
+        let mut game = create_test_game();
+        game.properties.iter_mut().find(|p| p.id == 15).unwrap().owner_id = Some("player2".to_string());
+        game.players[0].position = 7;
+        game.phase = GamePhase::EndTurn;
+
+        let card = Card {
+            id: 5,
+            title: "Naik Bikun".to_string(),
+            description: String::new(),
+            effect: CardEffect::AdvanceToNearest {
+                group: crate::game::board::PropertyGroup::Railroad,
+                rent_multiplier: 2,
+                rent_per_pip: None,
+            },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &card);
+        assert_eq!(game.players[0].position, 15);
+
+        // Charged on landing, not left to the player
+        assert_eq!(game.players[0].money, 1500000 - 50_000);
+        assert_eq!(game.players[1].money, 1500000 + 50_000);
+        assert!(crate::game::actions::handle_pay_rent(&mut game, "player1").is_err());
+        assert!(game.next_turn("player1").is_ok());
+    }
+
+    #[test]
+    fn test_nearest_utility_card_charges_per_pip() {
+        use crate::game::state::{Card, CardEffect};
+        let mut game = create_test_game();
+        game.properties.iter_mut().find(|p| p.id == 28).unwrap().owner_id = Some("player2".to_string());
+        game.players[0].position = 22;
+        game.last_dice_roll = Some((3, 4));
+
+        let card = Card {
+            id: 6,
+            title: "Ke Perpustakaan".to_string(),
+            description: String::new(),
+            effect: CardEffect::AdvanceToNearest {
+                group: crate::game::board::PropertyGroup::Utility,
+                rent_multiplier: 1,
+                rent_per_pip: Some(10_000),
+            },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &card);
+        assert_eq!(game.players[0].position, 28);
+        assert_eq!(game.rent_due(28, 7), 70_000);
+        assert_eq!(game.players[0].money, 1500000 - 70_000);
+        assert_eq!(game.players[1].money, 1500000 + 70_000);
+    }
+
+    #[test]
+    fn test_rent_follows_schedule() {
+        let mut game = create_test_game();
+        for id in [37, 39] {
+            game.properties.iter_mut().find(|p| p.id == id).unwrap().owner_id = Some("player1".to_string());
+        }
+
+        // Unimproved monopoly doubles base rent
+        assert_eq!(game.calculate_rent(39, 7), 100_000);
+
+        game.properties.iter_mut().find(|p| p.id == 39).unwrap().houses = 3;
+        assert_eq!(game.calculate_rent(39, 7), 1_400_000);
+
+        game.properties.iter_mut().find(|p| p.id == 39).unwrap().houses = 5;
+        assert_eq!(game.calculate_rent(39, 7), 2_000_000);
+    }
+
+    #[test]
+    fn test_railroad_and_utility_rent() {
+        let mut game = create_test_game();
+        for id in [5, 15, 25, 12] {
+            game.properties.iter_mut().find(|p| p.id == id).unwrap().owner_id = Some("player1".to_string());
+        }
+
+        assert_eq!(game.calculate_rent(5, 7), 100_000);
+        assert_eq!(game.calculate_rent(12, 7), 28_000);
+
+        game.properties.iter_mut().find(|p| p.id == 28).unwrap().owner_id = Some("player1".to_string());
+        assert_eq!(game.calculate_rent(12, 7), 70_000);
+    }
+
+    #[test]
+    fn test_building_cost_from_board() {
+        let mut game = create_test_game();
+        for id in [31, 32, 34] {
+            game.properties.iter_mut().find(|p| p.id == id).unwrap().owner_id = Some("player1".to_string());
+        }
+
+        assert!(game.handle_buy_building("player1".to_string(), 31).is_ok());
+        assert_eq!(game.players[0].money, 1500000 - 200_000);
+
+        assert!(game.handle_sell_building("player1".to_string(), 31).is_ok());
+        assert_eq!(game.players[0].money, 1500000 - 100_000);
+    }
+
+    #[test]
+    fn test_hotel_sale_needs_houses_in_bank() {
+        let mut game = create_test_game();
+        for id in [37, 39] {
+            let property = game.properties.iter_mut().find(|p| p.id == id).unwrap();
+            property.owner_id = Some("player1".to_string());
+            property.houses = 5;
+        }
+        game.total_hotels -= 2;
+        game.total_houses = 3;
+
+        assert!(game.handle_sell_building("player1".to_string(), 39).is_err());
+        assert_eq!(game.players[0].money, 1500000);
+        assert_eq!(game.properties.iter().find(|p| p.id == 39).unwrap().houses, 5);
+        assert_eq!((game.total_houses, game.total_hotels), (3, 10));
+    }
+
+    #[test]
+    fn test_decline_property_starts_auction() {
+        let mut game = create_test_game();
+        game.players[0].position = 39; // Kedokteran
+        game.phase = GamePhase::EndTurn;
+
+        let result = game.handle_decline_property("player1", 100);
+        assert!(result.is_ok());
+        assert_eq!(game.phase, GamePhase::Auction);
+
+        let auction = game.auction.as_ref().unwrap();
+        assert_eq!(auction.property_id, 39);
+        assert_eq!(auction.bidders.len(), 2);
+        assert_eq!(auction.return_phase, GamePhase::EndTurn);
+    }
+
+    #[test]
+    fn test_auction_won_when_others_pass() {
+        let mut game = create_test_game();
+        game.players[0].position = 39;
+        game.phase = GamePhase::EndTurn;
+        game.handle_decline_property("player1", 100).unwrap();
+
+        // Bid below the minimum is rejected
+        assert!(game.handle_place_bid("player2", 5_000, 101).is_err());
+
+        assert!(game.handle_place_bid("player2", 250_000, 101).is_ok());
+        assert!(game.handle_pass_auction("player2").is_err()); // Highest bidder cannot pass
+        assert!(game.handle_pass_auction("player1").is_ok());
+
+        assert!(game.auction.is_none());
+        assert_eq!(game.phase, GamePhase::EndTurn);
+        let prop = game.properties.iter().find(|p| p.id == 39).unwrap();
+        assert_eq!(prop.owner_id, Some("player2".to_string()));
+        assert_eq!(game.players[1].money, 1500000 - 250_000);
+    }
+
+    #[test]
+    fn test_auction_expires_to_highest_bidder() {
+        let mut game = create_test_game();
+        game.players[0].position = 1;
+        game.phase = GamePhase::EndTurn;
+        game.handle_decline_property("player1", 100).unwrap();
+
+        game.handle_place_bid("player1", 20_000, 102).unwrap();
+        // Countdown restarts with each bid
+        assert!(game.resolve_expired_auction(110).is_empty());
+        assert!(!game.resolve_expired_auction(112).is_empty());
+
+        let prop = game.properties.iter().find(|p| p.id == 1).unwrap();
+        assert_eq!(prop.owner_id, Some("player1".to_string()));
+        assert_eq!(game.players[0].money, 1500000 - 20_000);
+    }
+
+    #[test]
+    fn test_auction_without_bids_keeps_property_in_bank() {
+        let mut game = create_test_game();
+        game.players[0].position = 1;
+        game.phase = GamePhase::EndTurn;
+        game.handle_decline_property("player1", 100).unwrap();
+
+        game.handle_pass_auction("player1").unwrap();
+        game.handle_pass_auction("player2").unwrap();
+
+        assert!(game.auction.is_none());
+        assert_eq!(game.phase, GamePhase::EndTurn);
+        assert!(game.properties.iter().find(|p| p.id == 1).unwrap().owner_id.is_none());
+    }
+
+    #[test]
+    fn test_house_rules_drive_salary_bail_and_tax() {
+        let mut game = create_test_game();
+        game.rules.go_salary = 400_000;
+        game.rules.bail_amount = 100_000;
+        game.rules.tax_percent = 50;
+
+        game.players[0].position = 38;
+        game.move_player(0, 6);
+        assert_eq!(game.players[0].position, 4);
+        assert_eq!(game.players[0].money, 1500000 + 400_000);
+
+        // Tile 4 prints 200_000 of tax, halved by the rule
+        game.handle_landing(0);
+        assert_eq!(game.players[0].money, 1500000 + 400_000 - 100_000);
+
+        game.players[1].is_in_jail = true;
+        game.pay_bail(1).unwrap();
+        assert_eq!(game.players[1].money, 1500000 - 100_000);
+    }
+
+    #[test]
+    fn test_free_parking_jackpot() {
+        let mut game = create_test_game();
+        game.rules.free_parking_jackpot = true;
+
+        game.players[0].position = 4;
+        game.handle_landing(0);
+        assert_eq!(game.free_parking_pot, 200_000);
+
+        game.players[1].position = 20;
+        let events = game.handle_landing(1);
+        assert!(events.iter().any(|e| matches!(e, crate::ws::messages::ServerMessage::FreeParkingPayout { amount: 200_000, .. })));
+        assert_eq!(game.players[1].money, 1500000 + 200_000);
+        assert_eq!(game.free_parking_pot, 0);
+    }
+
+    #[test]
+    fn test_taxes_leave_the_game_without_jackpot() {
+        let mut game = create_test_game();
+        game.players[0].position = 4;
+        game.handle_landing(0);
+        assert_eq!(game.free_parking_pot, 0);
+        assert_eq!(game.players[0].money, 1500000 - 200_000);
+    }
+
+    #[test]
+    fn test_unaffordable_rent_becomes_debt() {
+        let mut game = create_test_game();
+        game.properties.iter_mut().find(|p| p.id == 39).unwrap().owner_id = Some("player2".to_string());
+        game.properties.iter_mut().find(|p| p.id == 39).unwrap().houses = 5;
+        game.players[0].position = 39;
+        game.players[0].money = 100_000;
+        game.phase = GamePhase::EndTurn;
+
+        crate::game::actions::handle_pay_rent(&mut game, "player1").unwrap();
+        assert_eq!(game.phase, GamePhase::DebtResolution);
+        assert_eq!(game.debts.len(), 1);
+        assert_eq!(game.players[0].money, 100_000);
+        assert!(game.next_turn("player1").is_err());
+
+        // Not enough cash yet
+        assert!(game.handle_pay_debt("player1").is_err());
+
+        // Raise funds and settle
+        let rent = game.debts[0].amount;
+        game.players[0].money = rent;
+        game.handle_pay_debt("player1").unwrap();
+        assert!(game.debts.is_empty());
+        assert_eq!(game.phase, GamePhase::EndTurn);
+        assert_eq!(game.players[0].money, 0);
+        assert_eq!(game.players[1].money, 1500000 + rent);
+    }
+
+    #[test]
+    fn test_bankruptcy_requires_debt_and_pays_creditor() {
+        let mut game = create_test_game();
+        game.players.push(crate::game::state::PlayerState::new("player3", "Player 3", 1500000));
+        game.phase = GamePhase::EndTurn;
+        assert!(game.handle_declare_bankruptcy("player1").is_err());
+
+        game.properties.iter_mut().find(|p| p.id == 1).unwrap().owner_id = Some("player1".to_string());
+        game.players[0].money = 0;
+        game.charge(0, 50_000, Creditor::Player("player2".to_string()));
+        assert_eq!(game.phase, GamePhase::DebtResolution);
+
+        game.handle_declare_bankruptcy("player1").unwrap();
+        assert!(game.debts.is_empty());
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(game.players[game.current_turn].id, "player2");
+        assert_eq!(game.phase, GamePhase::Rolling);
+        let prop = game.properties.iter().find(|p| p.id == 1).unwrap();
+        assert_eq!(prop.owner_id, Some("player2".to_string()));
+    }
+
+    #[test]
+    fn test_bankruptcy_to_player_hands_over_everything() {
+        let mut game = create_test_game();
+        let browns: Vec<usize> = game.board.properties()
+            .filter(|(_, info)| info.group == crate::game::board::PropertyGroup::Brown)
+            .map(|(tile, _)| tile.id)
+            .collect();
+        let house_cost = game.board.property(browns[0]).unwrap().house_cost;
+        for property in game.properties.iter_mut().filter(|p| browns.contains(&p.id)) {
+            property.owner_id = Some("player1".to_string());
+            property.houses = 2;
+        }
+        game.properties.iter_mut().find(|p| p.id == 5).unwrap().owner_id = Some("player1".to_string());
+        game.properties.iter_mut().find(|p| p.id == 5).unwrap().is_mortgaged = true;
+        game.total_houses -= 2 * browns.len() as u8;
+        game.players[0].held_cards.push(crate::game::state::Card {
+            id: 0,
+            title: "Kartu Bebas Skorsing".to_string(),
+            description: String::new(),
+            effect: crate::game::state::CardEffect::GetOutOfJail,
+            is_chance: false,
+        });
+        game.players[0].money = 30_000;
+        game.charge(0, 5_000_000, Creditor::Player("player2".to_string()));
+
+        game.handle_declare_bankruptcy("player1").unwrap();
+        let proceeds = 2 * browns.len() as i32 * house_cost / 2;
+        assert_eq!(game.players[0].money, 1500000 + 30_000 + proceeds);
+        assert_eq!(game.players[0].jail_card_count(), 1);
+        assert_eq!(game.total_houses, 32);
+        assert!(game.properties.iter().filter(|p| browns.contains(&p.id)).all(|p| p.houses == 0 && p.owner_id.as_deref() == Some("player2")));
+        assert_eq!(game.mortgage_decisions.len(), 1);
+        assert_eq!(game.mortgage_decisions[0].property_id, 5);
+    }
+
+    #[test]
+    fn test_bankruptcy_to_bank_auctions_estates() {
+        let mut game = create_test_game();
+        let mut player3 = game.players[1].clone();
+        player3.id = "player3".to_string();
+        game.players.push(player3);
+        for id in [1, 3] {
+            let property = game.properties.iter_mut().find(|p| p.id == id).unwrap();
+            property.owner_id = Some("player1".to_string());
+            property.is_mortgaged = id == 3;
+        }
+        game.players[0].held_cards.push(crate::game::state::Card {
+            id: 0,
+            title: "Kartu Bebas Skorsing".to_string(),
+            description: String::new(),
+            effect: crate::game::state::CardEffect::GetOutOfJail,
+            is_chance: true,
+        });
+        let chance_cards = game.chance_deck.len();
+        game.players[0].money = 0;
+        game.charge(0, 50_000, Creditor::Bank);
+
+        game.handle_declare_bankruptcy("player1").unwrap();
+        assert_eq!(game.chance_deck.len(), chance_cards + 1);
+        assert_eq!(game.chance_deck.last().unwrap().effect, crate::game::state::CardEffect::GetOutOfJail);
+        assert_eq!(game.pending_auctions, vec![1, 3]);
+        assert!(game.properties.iter().filter(|p| p.id == 1 || p.id == 3).all(|p| p.owner_id.is_none() && !p.is_mortgaged));
+        assert!(game.handle_roll("player2").is_err());
+
+        game.auction_bank_estate(1000).unwrap();
+        assert_eq!(game.phase, GamePhase::Auction);
+        assert_eq!(game.auction.as_ref().unwrap().property_id, 1);
+        assert_eq!(game.pending_auctions, vec![3]);
+    }
+
+    #[test]
+    fn test_removed_players_estates_return_to_bank() {
+        let mut game = create_test_game();
+        game.rules.estate_policy = crate::game::rules::EstatePolicy::Auction;
+        for (id, houses, mortgaged) in [(1, 5, false), (3, 0, true)] {
+            let property = game.properties.iter_mut().find(|p| p.id == id).unwrap();
+            property.owner_id = Some("player2".to_string());
+            property.houses = houses;
+            property.is_mortgaged = mortgaged;
+        }
+        game.total_hotels -= 1;
+
+        let events = game.remove_player("player2");
+        assert!(events.iter().any(|e| matches!(e, crate::ws::messages::ServerMessage::EstateSettled { property_ids, .. } if property_ids == &vec![1, 3])));
+        assert!(game.properties.iter().filter(|p| p.id == 1 || p.id == 3).all(|p| p.owner_id.is_none() && p.houses == 0 && !p.is_mortgaged));
+        assert_eq!(game.total_hotels, 12);
+        assert_eq!(game.pending_auctions, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_collect_from_all_puts_short_players_in_debt() {
+        use crate::game::state::{Card, CardEffect};
+
+        let mut game = create_test_game();
+        game.players[1].money = 10_000;
+        let card = Card {
+            id: 99,
+            title: "Ulang tahun".to_string(),
+            description: "Terima dari setiap pemain".to_string(),
+            effect: CardEffect::CollectFromAll { amount: 50_000 },
+            is_chance: false,
+        };
+        game.apply_card_effect(0, &card);
+
+        assert_eq!(game.phase, GamePhase::DebtResolution);
+        assert_eq!(game.debts[0].debtor_id, "player2");
+        assert_eq!(game.debts[0].creditor, Creditor::Player("player1".to_string()));
+        assert_eq!(game.players[0].money, 1500000);
+    }
+
+    #[test]
+    fn test_turn_timeout_declines_then_ends_turn() {
+        let mut game = create_test_game();
+        game.players[0].position = 1;
+        game.phase = GamePhase::EndTurn;
+
+        game.handle_turn_timeout(100);
+        assert_eq!(game.phase, GamePhase::Auction);
+        game.resolve_expired_auction(200);
+        assert_eq!(game.phase, GamePhase::EndTurn);
+
+        // The tile cannot be sent to auction twice for the same landing
+        assert!(game.handle_decline_property("player1", 200).is_err());
+        game.handle_turn_timeout(200);
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.phase, GamePhase::Rolling);
+    }
+
+    #[test]
+    fn test_turn_timeout_rolls_for_idle_player() {
+        let mut game = create_test_game();
+        let events = game.handle_turn_timeout(100);
+        assert!(events.iter().any(|e| matches!(e, crate::ws::messages::ServerMessage::DiceRolled { .. })));
+        assert!(game.last_dice_roll.is_some());
+    }
+
+    #[test]
+    fn test_seed_reproduces_decks_and_dice() {
+        let new_game = |seed| GameState::with_board(
+            crate::game::board::Board::default(),
+            &crate::game::cards::DeckSet::default(),
+            crate::game::rules::HouseRules::default(),
+            crate::game::rng::GameRng::new(seed),
+        );
+        let mut a = new_game(42);
+        let mut b = new_game(42);
+        assert_eq!(a.chance_deck, b.chance_deck);
+        assert_eq!(a.community_chest_deck, b.community_chest_deck);
+        for _ in 0..20 {
+            assert_eq!(a.rng.roll_dice(), b.rng.roll_dice());
+        }
+    }
+
+    #[test]
+    fn test_scripted_dice_rejects_invalid_faces() {
+        let mut game = create_test_game();
+        assert!(game.rng.script_dice(&[(1, 7)]).is_err());
+        assert!(game.rng.script_dice(&[(0, 3)]).is_err());
+        game.rng.script_dice(&[(6, 6)]).unwrap();
+        assert_eq!(game.rng.roll_dice(), (6, 6));
+    }
+
+    #[test]
+    fn test_third_doubles_sends_player_to_jail() {
+        let mut game = create_test_game();
+        for id in [6, 14] {
+            game.properties.iter_mut().find(|p| p.id == id).unwrap().owner_id = Some("player1".to_string());
+        }
+        game.rng.script_dice(&[(3, 3), (4, 4), (2, 2)]).unwrap();
+
+        game.handle_roll("player1").unwrap();
+        assert_eq!(game.players[0].position, 6);
+        assert_eq!(game.phase, GamePhase::Rolling); // Doubles roll again
+        game.handle_roll("player1").unwrap();
+        assert_eq!(game.players[0].position, 14);
+        assert_eq!(game.players[0].doubles_count, 2);
+
+        game.handle_roll("player1").unwrap();
+        assert!(game.players[0].is_in_jail);
+        assert_eq!(game.players[0].position, game.board.jail_position());
+        assert_eq!(game.players[0].doubles_count, 0);
+        assert_eq!(game.phase, GamePhase::EndTurn);
+    }
+
+    #[test]
+    fn test_tile_reached_with_doubles_is_offered_before_rerolling() {
+        let mut game = create_test_game();
+        game.rng.script_dice(&[(3, 3), (4, 4), (1, 2)]).unwrap();
+
+        game.handle_roll("player1").unwrap();
+        assert_eq!(game.players[0].position, 6);
+        assert_eq!(game.phase, GamePhase::Rolling);
+        assert!(game.handle_roll("player1").is_err());
+
+        // Declining auctions the tile, then the player rolls again
+        game.handle_decline_property("player1", 100).unwrap();
+        assert_eq!(game.phase, GamePhase::Auction);
+        game.resolve_expired_auction(200);
+        assert_eq!(game.phase, GamePhase::Rolling);
+        assert!(game.handle_decline_property("player1", 200).is_err());
+        game.handle_roll("player1").unwrap();
+        assert_eq!(game.players[0].position, 14);
+
+        // Or buys it
+        crate::game::actions::handle_buy_property(&mut game, "player1").unwrap();
+        assert_eq!(game.properties.iter().find(|p| p.id == 14).unwrap().owner_id.as_deref(), Some("player1"));
+        game.handle_roll("player1").unwrap();
+        assert_eq!(game.phase, GamePhase::EndTurn);
+
+        // The next turn starts on last turn's tile, which is not on offer
+        game.next_turn("player1").unwrap();
+        game.players[1].position = 1;
+        assert!(!game.offer_pending());
+        assert!(crate::game::actions::handle_buy_property(&mut game, "player2").is_err());
+    }
+
+    #[test]
+    fn test_doubles_release_from_jail() {
+        let mut game = create_test_game();
+        game.send_to_jail(0);
+        game.phase = GamePhase::Rolling;
+        game.rng.script_dice(&[(1, 2), (5, 5)]).unwrap();
+
+        game.handle_roll("player1").unwrap();
+        assert!(game.players[0].is_in_jail);
+        assert_eq!(game.players[0].jail_turns, 1);
+        assert_eq!(game.players[0].position, 10);
+        assert_eq!(game.phase, GamePhase::EndTurn);
+
+        game.phase = GamePhase::Rolling;
+        game.handle_roll("player1").unwrap();
+        assert!(!game.players[0].is_in_jail);
+        assert_eq!(game.players[0].position, 20);
+    }
+
+    #[test]
+    fn test_third_failed_jail_roll_forces_bail() {
+        let mut game = create_test_game();
+        game.send_to_jail(0);
+        game.players[0].jail_turns = 2;
+        game.phase = GamePhase::Rolling;
+        game.rng.script_dice(&[(1, 2)]).unwrap();
+
+        game.handle_roll("player1").unwrap();
+        assert!(!game.players[0].is_in_jail);
+        assert_eq!(game.players[0].money, 1500000 - game.rules.bail_amount);
+        assert_eq!(game.players[0].position, 13);
+        assert_eq!(game.phase, GamePhase::EndTurn);
+    }
 }
@@ -4,3 +4,10 @@ pub mod board;
 pub mod actions;
 pub mod cards;
 pub mod trade;
+pub mod auction;
+pub mod debt;
+pub mod rules;
+pub mod rng;
+pub mod command;
+pub mod log;
+pub mod bot;
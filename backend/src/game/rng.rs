@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Random source owned by a game. Its whole state is serialized with
+/// `GameState`, so replaying the same commands from the same state rolls the
+/// same dice, deck order and ids.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameRng {
+    seed: u64,
+    state: u64,
+    /// Debug mode: rolls handed out before any random ones
+    #[serde(default)]
+    scripted_dice: VecDeque<(u8, u8)>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed, scripted_dice: VecDeque::new() }
+    }
+
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn roll_dice(&mut self) -> (u8, u8) {
+        if let Some(dice) = self.scripted_dice.pop_front() {
+            return dice;
+        }
+        (self.random_range(1..=6), self.random_range(1..=6))
+    }
+
+    /// Queues the outcomes of the next rolls
+    pub fn script_dice(&mut self, rolls: &[(u8, u8)]) -> Result<(), String> {
+        if let Some(&(a, b)) = rolls.iter().find(|(a, b)| !(1..=6).contains(a) || !(1..=6).contains(b)) {
+            return Err(format!("Invalid dice ({}, {})", a, b));
+        }
+        self.scripted_dice.extend(rolls.iter().copied());
+        Ok(())
+    }
+
+    /// Random UUID-formatted id for trades and auctions
+    pub fn next_id(&mut self) -> String {
+        let mut bytes = [0u8; 16];
+        self.fill_bytes(&mut bytes);
+        uuid::Builder::from_random_bytes(bytes).into_uuid().to_string()
+    }
+}
+
+/// SplitMix64: tiny, fast and good enough for dice
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        rand::rand_core::impls::fill_bytes_via_next(self, dst)
+    }
+}
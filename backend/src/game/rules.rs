@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens to the estates of a player who is kicked or leaves mid-game
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum EstatePolicy {
+    /// Buildings go back to the bank's stock, mortgages are cleared and the
+    /// properties are left unowned
+    #[default]
+    ReturnToBank,
+    /// As `ReturnToBank`, then every property is auctioned
+    Auction,
+    /// A bot takes over the seat with everything the player had
+    HandToBot,
+}
+
+/// Money and bank values a room can tune before the game starts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HouseRules {
+    pub starting_money: i32,
+    pub go_salary: i32,
+    pub bail_amount: i32,
+    /// Percentage applied to the tax amounts printed on the board
+    pub tax_percent: i32,
+    pub total_houses: u8,
+    pub total_hotels: u8,
+    /// Taxes and card payments go into a pot won by landing on Free Parking
+    #[serde(default)]
+    pub free_parking_jackpot: bool,
+    /// Seconds a player has to act before the server plays for them, 0 (the
+    /// default) disables the timer
+    #[serde(default)]
+    pub turn_timeout_secs: u64,
+    /// Seconds a trade proposal waits for an answer before it is cancelled, 0 keeps it open
+    #[serde(default = "default_trade_expiry_secs")]
+    pub trade_expiry_secs: u64,
+    /// Trade proposals a player can have waiting for an answer at once
+    #[serde(default = "default_max_open_trades")]
+    pub max_open_trades: usize,
+    #[serde(default)]
+    pub estate_policy: EstatePolicy,
+}
+
+fn default_trade_expiry_secs() -> u64 {
+    120
+}
+
+fn default_max_open_trades() -> usize {
+    3
+}
+
+impl Default for HouseRules {
+    fn default() -> Self {
+        Self {
+            starting_money: 1_500_000,
+            go_salary: 200_000,
+            bail_amount: 50_000,
+            tax_percent: 100,
+            total_houses: 32,
+            total_hotels: 12,
+            free_parking_jackpot: false,
+            turn_timeout_secs: 0,
+            trade_expiry_secs: default_trade_expiry_secs(),
+            max_open_trades: default_max_open_trades(),
+            estate_policy: EstatePolicy::default(),
+        }
+    }
+}
+
+impl HouseRules {
+    pub fn validate(&self) -> Result<(), String> {
+        // Capped well below i32::MAX so money sums cannot overflow
+        if !(1..=10_000_000).contains(&self.starting_money) {
+            return Err("Starting money must be between 1 and 10,000,000".to_string());
+        }
+        if !(0..=1_000_000).contains(&self.go_salary) || !(0..=1_000_000).contains(&self.bail_amount) {
+            return Err("GO salary and bail must be between 0 and 1,000,000".to_string());
+        }
+        if !(0..=500).contains(&self.tax_percent) {
+            return Err("Tax percentage must be between 0 and 500".to_string());
+        }
+        if self.turn_timeout_secs != 0 && !(60..=180).contains(&self.turn_timeout_secs) {
+            return Err("Turn timer must be off or between 60 and 180 seconds".to_string());
+        }
+        if self.trade_expiry_secs != 0 && !(30..=600).contains(&self.trade_expiry_secs) {
+            return Err("Trade expiry must be off or between 30 and 600 seconds".to_string());
+        }
+        if !(1..=10).contains(&self.max_open_trades) {
+            return Err("Open trade limit must be between 1 and 10".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn tax(&self, printed_amount: i32) -> i32 {
+        printed_amount * self.tax_percent / 100
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_rules_are_bounded() {
+        assert!(HouseRules::default().validate().is_ok());
+        let too_much = [
+            HouseRules { starting_money: 10_000_001, ..HouseRules::default() },
+            HouseRules { starting_money: 0, ..HouseRules::default() },
+            HouseRules { go_salary: i32::MAX, ..HouseRules::default() },
+            HouseRules { bail_amount: 1_000_001, ..HouseRules::default() },
+            HouseRules { bail_amount: -1, ..HouseRules::default() },
+        ];
+        for rules in too_much {
+            assert!(rules.validate().is_err(), "{:?} was accepted", rules);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::game::board::PropertyGroup;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum GamePhase {
@@ -6,6 +7,8 @@ pub enum GamePhase {
     Rolling,
     Moving,
     EndTurn,
+    Auction,
+    DebtResolution,
     GameOver,
 }
 
@@ -22,6 +25,29 @@ pub struct PlayerState {
     pub held_cards: Vec<Card>,
 }
 
+impl PlayerState {
+    /// A player standing on GO with nothing but cash
+    #[cfg(test)]
+    pub fn new(id: &str, name: &str, money: i32) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            money,
+            position: 0,
+            color: "red".to_string(),
+            is_in_jail: false,
+            jail_turns: 0,
+            doubles_count: 0,
+            held_cards: Vec::new(),
+        }
+    }
+
+    /// Get-Out-of-Jail cards in the player's hand
+    pub fn jail_card_count(&self) -> u8 {
+        self.held_cards.iter().filter(|c| c.effect == CardEffect::GetOutOfJail).count() as u8
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PropertyState {
     pub id: usize,
@@ -31,18 +57,68 @@ pub struct PropertyState {
     pub is_mortgaged: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Card {
     pub id: u8,
     pub title: String,
     pub description: String,
-    pub effect_type: String, // collect, pay, advance, etc.
-    pub value: Option<i32>,
-    pub target_id: Option<usize>, // For movement to specific tile
+    pub effect: CardEffect,
+    /// Deck the card is returned to once held: Chance, or else Community Chest
+    #[serde(default)]
+    pub is_chance: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum CardEffect {
+    /// Receive money from the bank
+    Collect { amount: i32 },
+    /// Pay money to the bank
+    Pay { amount: i32 },
+    /// Move forward to a specific tile, collecting GO salary when passing it
+    Advance { target: usize },
+    /// Move backward a number of tiles
+    Back { steps: usize },
+    GoToJail,
+    /// Pay the bank per house and per hotel owned
+    Repair { per_house: i32, per_hotel: i32 },
+    /// Receive money from every other player
+    CollectFromAll { amount: i32 },
+    /// Pay money to every other player
+    PayEachPlayer { amount: i32 },
+    /// Advance to the nearest tile of a group. If it is owned, the owner is paid
+    /// `rent_multiplier` times the normal rent, or `rent_per_pip` times the dice
+    /// total when set (the classic "ten times the dice" utility card), on landing.
+    AdvanceToNearest {
+        group: PropertyGroup,
+        #[serde(default = "default_rent_multiplier")]
+        rent_multiplier: i32,
+        #[serde(default)]
+        rent_per_pip: Option<i32>,
+    },
+    /// Kept by the player until used to leave jail
+    GetOutOfJail,
+}
+
+fn default_rent_multiplier() -> i32 {
+    1
+}
+
+/// Rent rule set by a card for the tile it moved the player to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RentOverride {
+    Multiplier(i32),
+    PerDicePip(i32),
 }
 
 use std::collections::HashMap;
-use crate::game::trade::TradeProposal;
+use crate::game::trade::{MortgageDecision, TradeProposal};
+use crate::game::auction::Auction;
+use crate::game::debt::Debt;
+use crate::game::board::Board;
+use crate::game::cards::DeckSet;
+use crate::game::rules::HouseRules;
+use crate::game::rng::GameRng;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
@@ -51,26 +127,51 @@ pub struct GameState {
     pub current_turn: usize,
     pub phase: GamePhase,
     pub rent_paid: bool,
+    /// The current tile was declined (and auctioned) since the player landed
+    /// on it, or the turn started on it, so it is not on offer
+    pub offer_declined: bool,
+    pub rent_override: Option<RentOverride>,
     pub chance_deck: Vec<Card>,
     pub community_chest_deck: Vec<Card>,
     pub active_trades: HashMap<String, TradeProposal>,
+    /// Mortgaged properties received in trades, waiting on their new owner
+    #[serde(default)]
+    pub mortgage_decisions: Vec<MortgageDecision>,
     pub total_houses: u8,
     pub total_hotels: u8,
     pub last_dice_roll: Option<(u8, u8)>,
     pub winner: Option<String>,
+    pub auction: Option<Auction>,
+    /// Estates returned to the bank by a bankruptcy, auctioned one at a time
+    #[serde(default)]
+    pub pending_auctions: Vec<usize>,
+    pub debts: Vec<Debt>,
+    /// Money collected for the Free Parking jackpot house rule
+    pub free_parking_pot: i32,
+    /// Sent to clients once, on join and with `GameStarted`, and restored
+    /// from the room's board after a reload
+    #[serde(skip)]
+    pub board: Board,
+    pub rules: HouseRules,
+    pub rng: GameRng,
 }
 
 impl GameState {
     pub fn new() -> Self {
-        let properties = crate::game::board::PROPERTIES.iter().map(|p| PropertyState {
-            id: p.id,
-            name: p.name.to_string(),
+        Self::with_board(Board::default(), &DeckSet::default(), HouseRules::default(), GameRng::from_entropy())
+    }
+
+    /// New game shuffled and played with `rng`; the same seed gives the same game
+    pub fn with_board(board: Board, decks: &DeckSet, rules: HouseRules, mut rng: GameRng) -> Self {
+        let properties = board.properties().map(|(tile, _)| PropertyState {
+            id: tile.id,
+            name: tile.name.clone(),
             owner_id: None,
             houses: 0,
             is_mortgaged: false,
         }).collect();
 
-        let (chance_deck, community_chest_deck) = crate::game::cards::initialize_decks();
+        let (chance_deck, community_chest_deck) = crate::game::cards::initialize_decks(decks, &mut rng);
 
         Self {
             players: Vec::new(),
@@ -78,13 +179,23 @@ impl GameState {
             current_turn: 0,
             phase: GamePhase::Waiting,
             rent_paid: false,
+            offer_declined: false,
+            rent_override: None,
             chance_deck,
             community_chest_deck,
             active_trades: HashMap::new(),
-            total_houses: 32,
-            total_hotels: 12,
+            mortgage_decisions: Vec::new(),
+            total_houses: rules.total_houses,
+            total_hotels: rules.total_hotels,
             last_dice_roll: None,
             winner: None,
+            auction: None,
+            pending_auctions: Vec::new(),
+            debts: Vec::new(),
+            free_parking_pot: 0,
+            board,
+            rules,
+            rng,
         }
     }
     
@@ -141,3 +252,17 @@ impl GameState {
         }
     }
 }
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Current Unix timestamp in seconds
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
@@ -12,6 +12,9 @@ pub enum TradeStatus {
 pub struct TradeOffer {
     pub money: i32,
     pub property_ids: Vec<usize>,
+    /// Number of Get-Out-of-Jail cards ("Kartu Bebas Skorsing") changing hands
+    #[serde(default)]
+    pub jail_cards: u8,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,4 +25,28 @@ pub struct TradeProposal {
     pub offer: TradeOffer,   // What initiator gives
     pub request: TradeOffer, // What initiator wants
     pub status: TradeStatus,
+    /// The proposal this one answers, for counter-offers
+    #[serde(default)]
+    pub counter_to: Option<String>,
+    /// Unix time at which the proposal is cancelled if still unanswered
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl TradeProposal {
+    /// Whether the player is one of the two parties
+    pub fn involves(&self, player_id: &str) -> bool {
+        self.initiator_id == player_id || self.target_player_id == player_id
+    }
+}
+
+/// A mortgaged property received in a trade. Its new owner either lifts the
+/// mortgage at `unmortgage_cost` or pays the bank `fee` and keeps it mortgaged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MortgageDecision {
+    pub player_id: String,
+    pub property_id: usize,
+    /// 10% interest on the mortgage value
+    pub fee: i32,
+    pub unmortgage_cost: i32,
 }
@@ -1,4 +1,6 @@
 use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     routing::{get, post},
     Router,
     Json,
@@ -7,8 +9,12 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
+use backend::game::board::Board;
+use backend::game::cards::DeckSet;
 use backend::room::manager::RoomManager;
+use backend::room::store::FileStore;
 use backend::ws::handler::ws_handler;
+use backend::ws::messages::public_state_value;
 use serde::Deserialize;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -29,6 +35,73 @@ async fn logs_handler(Json(entry): Json<LogEntry>) {
     }
 }
 
+#[derive(Deserialize, Debug)]
+struct ReplayQuery {
+    /// Replay only the log entries before this sequence number
+    upto: Option<u64>,
+}
+
+/// Whether the request carries the `ADMIN_TOKEN` as a bearer token
+fn is_admin(headers: &HeaderMap) -> bool {
+    let Ok(token) = std::env::var("ADMIN_TOKEN") else {
+        return false;
+    };
+    headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| !token.is_empty() && given == token)
+}
+
+/// Debug mode: rebuilds a room's game state from its game log, for
+/// reproducing bug reports. Only admins see the RNG and the players' trades.
+async fn replay_handler(
+    State(room_manager): State<Arc<RoomManager>>,
+    Path(room_code): Path<String>,
+    Query(query): Query<ReplayQuery>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let mut state = room_manager.replay(&room_code, query.upto).map_err(|e| (StatusCode::NOT_FOUND, e))?;
+    let value = if is_admin(&headers) {
+        serde_json::to_value(&state)
+    } else {
+        state.active_trades.clear();
+        public_state_value(&state)
+    };
+    value.map(Json).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[derive(Deserialize, Debug)]
+struct SeedRequest {
+    seed: u64,
+}
+
+/// Debug mode: fixes the seed of a room's next game
+async fn seed_handler(
+    State(room_manager): State<Arc<RoomManager>>,
+    Path(room_code): Path<String>,
+    Json(request): Json<SeedRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    room_manager.set_seed(&room_code, request.seed).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    room_manager.persist(&room_code);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, Debug)]
+struct DiceRequest {
+    dice: Vec<(u8, u8)>,
+}
+
+/// Debug mode: scripts the next rolls of a room's game
+async fn dice_handler(
+    State(room_manager): State<Arc<RoomManager>>,
+    Path(room_code): Path<String>,
+    Json(request): Json<DiceRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    room_manager.script_dice(&room_code, request.dice).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    room_manager.persist(&room_code);
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing
@@ -40,13 +113,37 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer().with_writer(non_blocking).with_ansi(false))
         .init();
 
-    // Initialize shared state
-    let room_manager = Arc::new(RoomManager::new());
+    // Load board editions and card decks, then initialize shared state
+    let boards_dir = std::env::var("BOARDS_DIR").unwrap_or_else(|_| "boards".to_string());
+    let boards = Board::load_dir(std::path::Path::new(&boards_dir));
+    let decks_dir = std::env::var("DECKS_DIR").unwrap_or_else(|_| "decks".to_string());
+    let deck_sets = DeckSet::load_dir(std::path::Path::new(&decks_dir));
+    let rooms_dir = std::env::var("ROOMS_DIR").unwrap_or_else(|_| "data/rooms".to_string());
+    let store = FileStore::new(&rooms_dir).expect("room store directory is writable");
+    // Logs of closed rooms are kept this long for replays
+    let log_retention_days = std::env::var("LOG_RETENTION_DAYS").ok().and_then(|v| v.parse().ok()).unwrap_or(30);
+    let pruned = store.prune_logs(std::time::Duration::from_secs(log_retention_days * 24 * 60 * 60));
+    tracing::info!("Pruned {} game logs older than {} days", pruned, log_retention_days);
+    let room_manager = Arc::new(RoomManager::with_content(boards, deck_sets, Arc::new(store)));
+    let restored = room_manager.restore();
+    tracing::info!("Restored {} rooms from {}", restored, rooms_dir);
 
     // Build our application with a route
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/ws", get(ws_handler))
-        .route("/api/logs", post(logs_handler))
+        .route("/api/logs", post(logs_handler));
+
+    // Seeds and scripted dice let anyone rig a game, and replays show its
+    // state, so they stay off unless asked for
+    if std::env::var("DEBUG_ROUTES").is_ok_and(|v| v == "1") {
+        tracing::warn!("Debug routes enabled: room seeds and dice can be scripted and games replayed");
+        app = app
+            .route("/api/rooms/:room_code/replay", get(replay_handler))
+            .route("/api/rooms/:room_code/debug/seed", post(seed_handler))
+            .route("/api/rooms/:room_code/debug/dice", post(dice_handler));
+    }
+
+    let app = app
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
         .with_state(room_manager);
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use crate::room::room::Room;
+use crate::ws::messages::ServerMessage;
+
+/// Chat messages kept per room and replayed to reconnecting players
+pub const CHAT_HISTORY_LEN: usize = 100;
+/// Longest chat message, in characters
+pub const MAX_CHAT_LEN: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub id: u64,
+    pub sender_id: String,
+    pub sender_name: String,
+    pub text: String,
+    /// Recipient of a whisper; `None` for messages to the whole room
+    pub to: Option<String>,
+    pub at: u64, // Unix timestamp in seconds
+}
+
+impl ChatMessage {
+    /// Whether `viewer_id` may read the message. Whispers are only for their
+    /// sender and recipient.
+    pub fn visible_to(&self, viewer_id: Option<&str>) -> bool {
+        match &self.to {
+            None => true,
+            Some(to) => viewer_id.is_some_and(|id| id == to || id == self.sender_id),
+        }
+    }
+}
+
+impl Room {
+    /// Posts a message to the room, or whispers it to `to`
+    pub fn send_chat(&mut self, sender_id: &str, text: &str, to: Option<String>, now: u64) -> Result<ServerMessage, String> {
+        let sender = self.players.get(sender_id).ok_or("Only players can chat")?;
+        if self.muted.contains(sender_id) {
+            return Err("You are muted".to_string());
+        }
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Message is empty".to_string());
+        }
+        if text.chars().count() > MAX_CHAT_LEN {
+            return Err(format!("Message is too long (max {} characters)", MAX_CHAT_LEN));
+        }
+        if let Some(to) = &to {
+            if to == sender_id {
+                return Err("You cannot whisper to yourself".to_string());
+            }
+            if !self.players.contains_key(to) {
+                return Err("Player not found".to_string());
+            }
+        }
+
+        let message = ChatMessage {
+            id: self.chat_seq,
+            sender_id: sender_id.to_string(),
+            sender_name: sender.name.clone(),
+            text: text.to_string(),
+            to,
+            at: now,
+        };
+        self.chat_seq += 1;
+        self.chat.push_back(message.clone());
+        if self.chat.len() > CHAT_HISTORY_LEN {
+            self.chat.pop_front();
+        }
+        Ok(ServerMessage::Chat { message })
+    }
+
+    /// The history `viewer_id` may read, oldest first
+    pub fn chat_history(&self, viewer_id: Option<&str>) -> ServerMessage {
+        ServerMessage::ChatHistory {
+            messages: self.chat.iter().filter(|m| m.visible_to(viewer_id)).cloned().collect(),
+        }
+    }
+
+    /// Host-only: stops or allows a player's chat messages
+    pub fn set_muted(&mut self, host_id: &str, target_id: &str, muted: bool) -> Result<ServerMessage, String> {
+        self.check_host(host_id)?;
+        if !self.players.contains_key(target_id) {
+            return Err("Player not found".to_string());
+        }
+        if muted {
+            self.muted.insert(target_id.to_string());
+        } else {
+            self.muted.remove(target_id);
+        }
+        Ok(ServerMessage::PlayerMuted { player_id: target_id.to_string(), muted })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::cards::DeckSet;
+    use crate::room::player::Player;
+    use crate::room::room::VoteKind;
+
+    fn chat_room() -> Room {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        for id in ["a", "b", "c"] {
+            room.add_player(Player::new(id, id.to_uppercase()));
+        }
+        room
+    }
+
+    #[test]
+    fn test_chat_history_hides_other_players_whispers() {
+        let mut room = chat_room();
+        room.send_chat("a", "hello all", None, 1000).unwrap();
+        room.send_chat("a", "psst", Some("b".to_string()), 1001).unwrap();
+        assert!(room.send_chat("a", " ", None, 1002).is_err());
+        assert!(room.send_chat("a", &"x".repeat(MAX_CHAT_LEN + 1), None, 1002).is_err());
+        assert!(room.send_chat("a", "me", Some("a".to_string()), 1002).is_err());
+
+        let count = |viewer| match room.chat_history(viewer) {
+            ServerMessage::ChatHistory { messages } => messages.len(),
+            _ => unreachable!(),
+        };
+        assert_eq!(count(Some("b")), 2);
+        assert_eq!(count(Some("c")), 1);
+        assert_eq!(count(None), 1);
+
+        for i in 0..CHAT_HISTORY_LEN {
+            room.send_chat("c", &i.to_string(), None, 1003).unwrap();
+        }
+        assert_eq!(room.chat.len(), CHAT_HISTORY_LEN);
+        assert_eq!(room.chat.front().unwrap().text, "0");
+    }
+
+    #[test]
+    fn test_mute_by_host_and_by_vote() {
+        let mut room = chat_room();
+        let host = room.host_id.clone().unwrap();
+        let others: Vec<String> = room.players.keys().filter(|id| **id != host).cloned().collect();
+
+        assert!(room.set_muted(&others[0], &others[1], true).is_err());
+        room.set_muted(&host, &others[0], true).unwrap();
+        assert!(room.send_chat(&others[0], "hi", None, 1000).is_err());
+        room.set_muted(&host, &others[0], false).unwrap();
+        assert!(room.send_chat(&others[0], "hi", None, 1000).is_ok());
+
+        room.start_vote(&host, &others[1], VoteKind::Mute, 1000).unwrap();
+        let events = room.cast_vote(&others[0], true, 1001);
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::PlayerMuted { muted: true, .. })));
+        assert!(room.players.contains_key(&others[1]));
+        assert!(room.send_chat(&others[1], "hi", None, 1002).is_err());
+    }
+}
@@ -1,35 +1,214 @@
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
+use crate::game::board::{Board, DEFAULT_BOARD_ID};
+use crate::game::cards::DeckSet;
+use crate::game::command::GameCommand;
+use crate::game::state::{GameState, unix_now};
 use crate::room::room::Room;
-use crate::room::player::Player;
+use crate::room::player::{Player, Presence, Spectator};
+use crate::room::settings::RoomSettings;
+use crate::room::store::{MemoryStore, RoomStore, StoreWriter};
+use crate::room::timer::spawn_room_ticker;
+use crate::ws::messages::ServerMessage;
 
 #[derive(Clone)]
 pub struct RoomManager {
     pub rooms: Arc<DashMap<String, Room>>,
+    pub boards: Arc<HashMap<String, Board>>,
+    pub deck_sets: Arc<HashMap<String, DeckSet>>,
+    pub store: Arc<dyn RoomStore>,
+    writer: StoreWriter,
+}
+
+impl Default for RoomManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RoomManager {
     pub fn new() -> Self {
+        let board = Board::default();
+        let decks = DeckSet::default();
+        Self::with_content(
+            HashMap::from([(board.id.clone(), board)]),
+            HashMap::from([(decks.id.clone(), decks)]),
+            Arc::new(MemoryStore::default()),
+        )
+    }
+
+    pub fn with_content(boards: HashMap<String, Board>, deck_sets: HashMap<String, DeckSet>, store: Arc<dyn RoomStore>) -> Self {
+        let rooms = Arc::new(DashMap::new());
         Self {
-            rooms: Arc::new(DashMap::new()),
+            writer: StoreWriter::spawn(store.clone(), rooms.clone()),
+            rooms,
+            boards: Arc::new(boards),
+            deck_sets: Arc::new(deck_sets),
+            store,
+        }
+    }
+
+    /// Reloads the rooms saved in the store and restarts their timers.
+    /// Returns the number of rooms restored.
+    pub fn restore(&self) -> usize {
+        let rooms = match self.store.load_all() {
+            Ok(rooms) => rooms,
+            Err(e) => {
+                tracing::error!("Cannot restore rooms: {}", e);
+                return 0;
+            }
+        };
+
+        let count = rooms.len();
+        let now = unix_now();
+        for mut room in rooms {
+            let room_id = room.id.clone();
+            // Every socket was lost with the old process
+            for player in room.players.values_mut().filter(|p| p.bot.is_none() && p.presence == Presence::Online) {
+                player.presence = Presence::Away { since: now };
+            }
+            // The game's board is not part of its snapshot
+            if let Some(state) = &mut room.game_state {
+                state.board = room.board.clone();
+            }
+            tracing::info!("Restored room {} ({} players)", room_id, room.players.len());
+            self.rooms.insert(room_id.clone(), room);
+            spawn_room_ticker(self.clone(), room_id);
+        }
+        count
+    }
+
+    /// Records a new socket for the player and tells the room if they came back
+    pub fn connect(&self, room_id: &str, player_id: &str) {
+        if let Some(mut room) = self.rooms.get_mut(room_id) {
+            let events = room.connect(player_id);
+            room.send_all(events);
+        }
+        self.persist(room_id);
+    }
+
+    /// Records a closed socket and tells the room if the player is now away
+    pub fn disconnect(&self, room_id: &str, player_id: &str) {
+        if let Some(mut room) = self.rooms.get_mut(room_id) {
+            let events = room.disconnect(player_id, unix_now());
+            room.send_all(events);
+        }
+        self.persist(room_id);
+    }
+
+    /// Queues new game log records and a snapshot of the room for the store
+    pub fn persist(&self, room_id: &str) {
+        self.writer.save(room_id);
+    }
+
+    /// Takes a player out of the room at their own request. The room closes
+    /// once no human player is left in it.
+    pub fn leave_room(&self, room_id: &str, player_id: &str, forfeit: bool) -> Result<(), String> {
+        let empty = {
+            let mut room = self.rooms.get_mut(room_id).ok_or("Room not found")?;
+            let events = room.leave(player_id, forfeit, unix_now())?;
+            room.send_all(events);
+            room.players.values().all(|p| p.bot.is_some())
+        };
+        if empty {
+            self.close_room(room_id);
+        } else {
+            self.persist(room_id);
+        }
+        Ok(())
+    }
+
+    /// Drops the room and its saved snapshot; its game log stays for replays.
+    /// Its timer stops on its next tick.
+    pub fn close_room(&self, room_id: &str) {
+        if self.rooms.remove(room_id).is_some() {
+            tracing::info!("Closing room {}", room_id);
         }
+        self.writer.delete(room_id);
     }
 
-    pub fn create_room(&self) -> String {
+    /// Applies a player's command to the room's game and sends out the result
+    pub fn dispatch(&self, room_id: &str, player_id: &str, command: GameCommand) -> Result<(), String> {
+        let mut room = self.rooms.get_mut(room_id).ok_or("Room not found")?;
+        if !room.players.contains_key(player_id) {
+            return Err("Only players can take game actions".to_string());
+        }
+        let events = room.dispatch(Some(player_id), command, unix_now())?;
+        // A player who acts again gets the full turn timer back
+        if let Some(clock) = room.turn_clock.as_mut().filter(|c| c.player_id == player_id) {
+            clock.auto = false;
+        }
+        room.send_all(events);
+        Ok(())
+    }
+
+    /// Debug mode: fixes the seed of the room's next game
+    pub fn set_seed(&self, room_id: &str, seed: u64) -> Result<(), String> {
+        let mut room = self.rooms.get_mut(room_id).ok_or("Room not found")?;
+        if room.game_state.is_some() {
+            return Err("Seed cannot be changed after the game has started".to_string());
+        }
+        room.seed = Some(seed);
+        Ok(())
+    }
+
+    /// Debug mode: queues the outcome of the next rolls in the room's game.
+    /// Goes through the game log so replays roll the same dice.
+    pub fn script_dice(&self, room_id: &str, dice: Vec<(u8, u8)>) -> Result<(), String> {
+        let mut room = self.rooms.get_mut(room_id).ok_or("Room not found")?;
+        room.dispatch(None, GameCommand::ScriptDice { dice }, unix_now())?;
+        Ok(())
+    }
+
+    /// Rebuilds the room's game as it was before log entry `upto`, or as it
+    /// ended when `upto` is `None`. Works on closed rooms too, for as long as
+    /// their log is kept.
+    pub fn replay(&self, room_id: &str, upto: Option<u64>) -> Result<GameState, String> {
+        self.store.load_log(room_id)?.replay(upto)
+    }
+
+    pub fn create_room(&self, board_id: Option<&str>) -> Result<String, String> {
+        let (board, decks) = self.resolve_edition(board_id.unwrap_or(DEFAULT_BOARD_ID))?;
+
         let room_id = Uuid::new_v4().to_string()[..6].to_uppercase();
-        let room = Room::new(room_id.clone());
+        let room = Room::new(room_id.clone(), board, decks);
         self.rooms.insert(room_id.clone(), room);
-        room_id
+        self.persist(&room_id);
+        spawn_room_ticker(self.clone(), room_id.clone());
+        Ok(room_id)
+    }
+
+    /// Looks up a board and the deck set it plays with
+    fn resolve_edition(&self, board_id: &str) -> Result<(Board, DeckSet), String> {
+        let board = self.boards.get(board_id)
+            .ok_or_else(|| format!("Unknown board '{}'", board_id))?
+            .clone();
+        let decks = self.deck_sets.get(&board.deck_set_id)
+            .ok_or_else(|| format!("Unknown deck set '{}' for board '{}'", board.deck_set_id, board_id))?
+            .clone();
+        Ok((board, decks))
+    }
+
+    pub fn update_settings(&self, room_id: &str, player_id: &str, settings: RoomSettings) -> Result<(), String> {
+        let (board, decks) = self.resolve_edition(&settings.board_id)?;
+        let mut room = self.rooms.get_mut(room_id).ok_or("Room not found")?;
+        room.update_settings(player_id, settings, board, decks)
+    }
+
+    /// Current lobby settings of a room, as sent to its players
+    pub fn settings_message(&self, room_id: &str) -> Option<ServerMessage> {
+        self.rooms.get(room_id).map(|room| ServerMessage::SettingsUpdated {
+            host_id: room.host_id.clone(),
+            settings: room.settings.clone(),
+        })
     }
 
     pub fn join_room(&self, room_id: &str, player_name: String) -> Option<(String, Vec<Player>)> {
         if let Some(mut room) = self.rooms.get_mut(room_id) {
             let player_id = Uuid::new_v4().to_string();
-            let player = Player {
-                id: player_id.clone(),
-                name: player_name,
-            };
+            let player = Player::new(player_id.clone(), player_name);
             room.add_player(player);
             
             let players = room.players.values().cloned().collect();
@@ -39,6 +218,29 @@ impl RoomManager {
         }
     }
     
+    /// Adds a spectator to the room and tells the players
+    pub fn spectate_room(&self, room_id: &str, name: String) -> Option<(Spectator, Vec<Player>)> {
+        let mut room = self.rooms.get_mut(room_id)?;
+        let spectator = room.add_spectator(name);
+        let spectators = ServerMessage::SpectatorsUpdated { spectators: room.spectator_list() };
+        room.send(spectators);
+        Some((spectator, room.players.values().cloned().collect()))
+    }
+
+    /// The board the room plays on, as sent to newcomers
+    pub fn board_message(&self, room_id: &str) -> Option<ServerMessage> {
+        self.rooms.get(room_id).map(|room| ServerMessage::BoardLoaded { board: room.board.clone() })
+    }
+
+    /// Who is watching the room, as sent to newcomers
+    pub fn spectators_message(&self, room_id: &str) -> Option<ServerMessage> {
+        self.rooms.get(room_id).map(|room| ServerMessage::SpectatorsUpdated { spectators: room.spectator_list() })
+    }
+
+    pub fn is_spectator(&self, room_id: &str, id: &str) -> bool {
+        self.rooms.get(room_id).is_some_and(|room| room.spectators.contains_key(id))
+    }
+
     pub fn get_room_players(&self, room_id: &str) -> Option<Vec<Player>> {
         self.rooms.get(room_id).map(|room| room.players.values().cloned().collect())
     }
@@ -1,3 +1,9 @@
 pub mod room;
 pub mod manager;
 pub mod player;
+pub mod settings;
+pub mod store;
+pub mod timer;
+pub mod presence;
+pub mod chat;
+pub mod outbox;
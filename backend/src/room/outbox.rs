@@ -0,0 +1,150 @@
+use tokio::sync::mpsc;
+use crate::room::room::Room;
+use crate::ws::messages::ServerMessage;
+
+/// The players (or spectators) an event is delivered to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Audience {
+    Everyone,
+    Player(String),
+    Players(Vec<String>),
+    /// Everyone but these players
+    Others(Vec<String>),
+}
+
+impl Audience {
+    /// Where an event goes when it is not addressed explicitly: whispers and
+    /// trade offers to the two players concerned, everything else to the
+    /// whole room
+    pub fn of(message: &ServerMessage) -> Audience {
+        match message {
+            ServerMessage::Chat { message } => match &message.to {
+                Some(to) => Audience::Players(vec![message.sender_id.clone(), to.clone()]),
+                None => Audience::Everyone,
+            },
+            ServerMessage::TradeProposed { proposal } => {
+                Audience::Players(vec![proposal.initiator_id.clone(), proposal.target_player_id.clone()])
+            }
+            ServerMessage::TradeNegotiating { initiator_id, target_player_id, .. } => {
+                Audience::Others(vec![initiator_id.clone(), target_player_id.clone()])
+            }
+            _ => Audience::Everyone,
+        }
+    }
+
+    pub fn includes(&self, id: &str) -> bool {
+        match self {
+            Audience::Everyone => true,
+            Audience::Player(player_id) => player_id == id,
+            Audience::Players(ids) => ids.iter().any(|player_id| player_id == id),
+            Audience::Others(ids) => !ids.iter().any(|player_id| player_id == id),
+        }
+    }
+}
+
+impl Room {
+    /// Opens an outbound channel for one socket of a player or spectator
+    pub fn subscribe(&mut self, id: &str) -> mpsc::UnboundedReceiver<ServerMessage> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.outboxes.entry(id.to_string()).or_default().push(tx);
+        rx
+    }
+
+    /// Delivers an event to its default audience
+    pub fn send(&mut self, message: ServerMessage) {
+        let audience = Audience::of(&message);
+        self.send_to(&audience, message);
+    }
+
+    pub fn send_all(&mut self, messages: impl IntoIterator<Item = ServerMessage>) {
+        for message in messages {
+            self.send(message);
+        }
+    }
+
+    /// Delivers an event to every open socket of the audience, as each of them
+    /// may see it. Sockets that have closed are dropped from the registry.
+    pub fn send_to(&mut self, audience: &Audience, message: ServerMessage) {
+        self.outboxes.retain(|id, senders| {
+            if audience.includes(id) {
+                let message = message.view_for(id);
+                senders.retain(|tx| tx.send(message.clone()).is_ok());
+            } else {
+                senders.retain(|tx| !tx.is_closed());
+            }
+            !senders.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::cards::DeckSet;
+    use crate::game::command::GameCommand;
+    use crate::game::trade::TradeOffer;
+    use crate::room::player::Player;
+
+    #[test]
+    fn test_events_reach_only_their_audience() {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        for id in ["a", "b", "c"] {
+            room.add_player(Player::new(id, id.to_uppercase()));
+        }
+        let mut a = room.subscribe("a");
+        let mut b = room.subscribe("b");
+        let mut c = room.subscribe("c");
+        let mut c_tab = room.subscribe("c");
+
+        let whisper = room.send_chat("a", "psst", Some("b".to_string()), 1000).unwrap();
+        room.send(whisper);
+        room.send_to(&Audience::Player("c".to_string()), ServerMessage::Error { message: "only c".to_string() });
+        room.send(ServerMessage::GameOver { winner_id: "a".to_string(), winner_name: "A".to_string() });
+
+        assert!(matches!(a.try_recv(), Ok(ServerMessage::Chat { .. })));
+        assert!(matches!(b.try_recv(), Ok(ServerMessage::Chat { .. })));
+        for rx in [&mut c, &mut c_tab] {
+            assert!(matches!(rx.try_recv(), Ok(ServerMessage::Error { .. })));
+            assert!(matches!(rx.try_recv(), Ok(ServerMessage::GameOver { .. })));
+        }
+        assert!(matches!(a.try_recv(), Ok(ServerMessage::GameOver { .. })));
+
+        // Closed sockets leave the registry
+        drop(c_tab);
+        drop(b);
+        room.send(ServerMessage::GameOver { winner_id: "a".to_string(), winner_name: "A".to_string() });
+        assert_eq!(room.outboxes["c"].len(), 1);
+        assert!(!room.outboxes.contains_key("b"));
+    }
+
+    #[test]
+    fn test_trade_offers_reach_only_their_parties() {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        for id in ["a", "b", "c"] {
+            room.add_player(Player::new(id, id.to_uppercase()));
+        }
+        room.start_game();
+        let mut a = room.subscribe("a");
+        let mut c = room.subscribe("c");
+
+        let offer = TradeOffer { money: 100, property_ids: Vec::new(), jail_cards: 0 };
+        let request = TradeOffer { money: 0, property_ids: Vec::new(), jail_cards: 0 };
+        let command = GameCommand::ProposeTrade { target_player_id: "b".to_string(), offer, request };
+        let events = room.dispatch(Some("a"), command, 1000).unwrap();
+        room.send_all(events);
+        let state = room.game_state.clone().unwrap();
+        room.send(ServerMessage::GameStateUpdate { state });
+
+        assert!(matches!(a.try_recv(), Ok(ServerMessage::TradeProposed { .. })));
+        assert!(matches!(a.try_recv(), Ok(ServerMessage::GameStateUpdate { state }) if state.active_trades.len() == 1));
+        assert!(matches!(c.try_recv(), Ok(ServerMessage::TradeNegotiating { .. })));
+        assert!(matches!(c.try_recv(), Ok(ServerMessage::GameStateUpdate { state }) if state.active_trades.is_empty()));
+
+        // The result is public once accepted
+        let trade_id = room.game_state.as_ref().unwrap().active_trades.keys().next().unwrap().clone();
+        let events = room.dispatch(Some("b"), GameCommand::AcceptTrade { trade_id }, 1001).unwrap();
+        room.send_all(events);
+        assert!(matches!(c.try_recv(), Ok(ServerMessage::TradeAccepted { proposal, .. }) if proposal.offer.money == 100));
+    }
+}
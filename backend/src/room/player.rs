@@ -1,7 +1,38 @@
 use serde::{Deserialize, Serialize};
+use crate::game::bot::BotStrategy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub id: String,
     pub name: String,
+    /// Set for bots played by the server
+    #[serde(default)]
+    pub bot: Option<BotStrategy>,
+    #[serde(default)]
+    pub presence: Presence,
+}
+
+impl Player {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self { id: id.into(), name: name.into(), bot: None, presence: Presence::Online }
+    }
+}
+
+/// Someone watching a room without taking part, e.g. a stream on a projector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spectator {
+    pub id: String,
+    pub name: String,
+}
+
+/// Whether a player is connected, and who plays their seat while they are not
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "status")]
+pub enum Presence {
+    #[default]
+    Online,
+    /// Disconnected at Unix time `since`; the seat waits for them
+    Away { since: u64 },
+    /// Disconnected for longer than the grace period; a cautious bot plays the seat
+    StandIn { since: u64 },
 }
@@ -0,0 +1,239 @@
+use crate::game::state::GamePhase;
+use crate::room::player::Presence;
+use crate::room::room::Room;
+use crate::ws::messages::ServerMessage;
+
+/// Seconds a disconnected player's seat waits for them before a bot takes it
+pub const AWAY_GRACE_SECS: u64 = 30;
+/// Seconds a lobby waits for any of its players to come back before it closes
+pub const LOBBY_IDLE_SECS: u64 = 600;
+
+impl Room {
+    /// Registers a socket for the player. Their first connection back hands
+    /// the seat back to them.
+    pub fn connect(&mut self, player_id: &str) -> Vec<ServerMessage> {
+        let Some(player) = self.players.get_mut(player_id) else {
+            return Vec::new();
+        };
+        *self.connections.entry(player_id.to_string()).or_default() += 1;
+
+        if std::mem::take(&mut player.presence) == Presence::Online {
+            return Vec::new();
+        }
+        tracing::info!("Player {} is back in room {}", player_id, self.id);
+        vec![ServerMessage::PlayerReturned { player_id: player_id.to_string() }]
+    }
+
+    /// Drops one of the player's sockets. Once none are left the player is
+    /// away, and their seat is taken over after `AWAY_GRACE_SECS`. Spectators
+    /// simply leave.
+    pub fn disconnect(&mut self, player_id: &str, now: u64) -> Vec<ServerMessage> {
+        if self.spectators.remove(player_id).is_some() {
+            return vec![ServerMessage::SpectatorsUpdated { spectators: self.spectator_list() }];
+        }
+        let Some(player) = self.players.get_mut(player_id) else {
+            return Vec::new();
+        };
+        let connections = self.connections.entry(player_id.to_string()).or_default();
+        *connections = connections.saturating_sub(1);
+        if *connections > 0 || player.presence != Presence::Online {
+            return Vec::new();
+        }
+
+        self.connections.remove(player_id);
+        player.presence = Presence::Away { since: now };
+        tracing::info!("Player {} left room {}", player_id, self.id);
+        vec![ServerMessage::PlayerAway {
+            player_id: player_id.to_string(),
+            takeover_at: now + AWAY_GRACE_SECS,
+        }]
+    }
+
+    /// Hands the seats of players away for longer than the grace period to
+    /// stand-in bots while a game is running
+    pub fn take_over_away_players(&mut self, now: u64) -> Vec<ServerMessage> {
+        if self.game_state.as_ref().is_none_or(|state| state.phase == GamePhase::GameOver) {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        for player in self.players.values_mut() {
+            if let Presence::Away { since } = player.presence {
+                if now >= since + AWAY_GRACE_SECS {
+                    tracing::info!("Stand-in bot takes over {} in room {}", player.id, self.id);
+                    player.presence = Presence::StandIn { since };
+                    events.push(ServerMessage::PlayerTakenOver { player_id: player.id.clone() });
+                }
+            }
+        }
+        events
+    }
+
+    /// Whether every player of a lobby has been away for `LOBBY_IDLE_SECS`
+    pub fn is_abandoned(&self, now: u64) -> bool {
+        if self.game_state.is_some() {
+            return false;
+        }
+        let mut humans = self.players.values().filter(|p| p.bot.is_none()).peekable();
+        humans.peek().is_some()
+            && humans.all(|p| matches!(p.presence, Presence::Away { since } if now >= since + LOBBY_IDLE_SECS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::cards::DeckSet;
+    use crate::room::player::Player;
+    use crate::room::room::GAME_OVER_GRACE_SECS;
+
+    #[test]
+    fn test_stand_in_plays_until_player_returns() {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room.start_game();
+        room.game_state.as_mut().unwrap().rules.turn_timeout_secs = 0;
+        let first = room.game_state.as_ref().unwrap().acting_player_id().unwrap().to_string();
+        for id in ["a", "b"] {
+            room.connect(id);
+        }
+
+        // A second tab closing does not make the player away
+        room.connect(&first);
+        assert!(room.disconnect(&first, 1000).is_empty());
+        let events = room.disconnect(&first, 1000);
+        assert!(matches!(&events[0], ServerMessage::PlayerAway { takeover_at, .. } if *takeover_at == 1000 + AWAY_GRACE_SECS));
+
+        // The seat waits during the grace period
+        room.tick(1001);
+        assert!(room.game_state.as_ref().unwrap().last_dice_roll.is_none());
+
+        let events = room.tick(1000 + AWAY_GRACE_SECS);
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::PlayerTakenOver { .. })));
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::DiceRolled { .. })));
+
+        let events = room.connect(&first);
+        assert!(matches!(&events[0], ServerMessage::PlayerReturned { .. }));
+        assert_eq!(room.players[&first].presence, Presence::Online);
+    }
+
+    #[test]
+    fn test_spectators_watch_without_playing() {
+        let manager = crate::room::manager::RoomManager::new();
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        manager.rooms.insert(room.id.clone(), room);
+
+        let (spectator, players) = manager.spectate_room("TEST", "Projector".to_string()).unwrap();
+        assert_eq!(players.len(), 2);
+        manager.rooms.get_mut("TEST").unwrap().start_game();
+        assert_eq!(manager.rooms.get("TEST").unwrap().game_state.as_ref().unwrap().players.len(), 2);
+
+        let command = crate::game::command::GameCommand::RollDice;
+        assert!(manager.dispatch("TEST", &spectator.id, command).is_err());
+
+        let events = manager.rooms.get_mut("TEST").unwrap().disconnect(&spectator.id, 1000);
+        assert!(matches!(&events[0], ServerMessage::SpectatorsUpdated { spectators } if spectators.is_empty()));
+    }
+
+    #[test]
+    fn test_finished_and_abandoned_rooms_close() {
+        let manager = crate::room::manager::RoomManager::new();
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        for id in ["a", "b"] {
+            room.connect(id);
+        }
+
+        room.disconnect("a", 1000);
+        room.disconnect("b", 1100);
+        assert!(!room.is_finished(1000 + LOBBY_IDLE_SECS));
+        assert!(room.is_finished(1100 + LOBBY_IDLE_SECS));
+        room.connect("a");
+        assert!(!room.is_finished(1100 + LOBBY_IDLE_SECS));
+
+        room.add_player(Player::new("c", "C"));
+        room.start_game();
+        assert!(!room.is_finished(1100 + LOBBY_IDLE_SECS));
+        room.leave("c", true, 2000).unwrap();
+        room.leave("b", true, 2000).unwrap();
+        assert_eq!(room.game_state.as_ref().unwrap().phase, GamePhase::GameOver);
+        // Its players get a while to look at the finished game
+        assert!(!room.is_finished(2000 + GAME_OVER_GRACE_SECS - 1));
+        assert!(room.is_finished(2000 + GAME_OVER_GRACE_SECS));
+
+        // The last player to leave closes the room
+        room.add_player(Player::new("b", "B"));
+        manager.rooms.insert(room.id.clone(), room);
+        manager.leave_room("TEST", "a", false).unwrap();
+        assert!(manager.rooms.contains_key("TEST"));
+        manager.leave_room("TEST", "b", false).unwrap();
+        assert!(!manager.rooms.contains_key("TEST"));
+    }
+
+    #[test]
+    fn test_players_leave_the_lobby_or_forfeit_the_game() {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        for id in ["a", "b", "c", "d", "e"] {
+            room.add_player(Player::new(id, id.to_uppercase()));
+        }
+        let events = room.leave("e", false, 1000).unwrap();
+        assert!(matches!(&events[0], ServerMessage::PlayerLeft { forfeited: false, .. }));
+        assert!(room.leave("e", false, 1000).is_err());
+        assert!(room.leave("a", true, 1000).is_err());
+
+        room.start_game();
+        assert!(room.leave("a", false, 1000).is_err());
+        let first = room.game_state.as_ref().unwrap().acting_player_id().unwrap().to_string();
+        let others: Vec<String> = ["a", "b", "c", "d"].iter().filter(|id| **id != first).map(|id| id.to_string()).collect();
+        let offer = crate::game::trade::TradeOffer { money: 10, property_ids: Vec::new(), jail_cards: 0 };
+        let command = crate::game::command::GameCommand::ProposeTrade {
+            target_player_id: others[0].clone(),
+            offer: offer.clone(),
+            request: offer,
+        };
+        room.dispatch(Some(&first), command, 1000).unwrap();
+        room.start_vote(&others[0], &others[1], crate::room::room::VoteKind::Mute, 1000).unwrap();
+        room.cast_vote(&first, false, 1000);
+
+        // Forfeiting on their own turn hands it to the next player
+        let events = room.leave(&first, true, 1001).unwrap();
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::TradeCancelled { .. })));
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::VoteUpdate { votes_against: 0, .. })));
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::TurnEnded { .. })));
+        assert!(!room.players.contains_key(&first));
+        let state = room.game_state.as_ref().unwrap();
+        assert!(state.active_trades.is_empty());
+        assert_eq!(state.phase, GamePhase::Rolling);
+        assert_ne!(state.acting_player_id(), Some(first.as_str()));
+
+        // A vote started by a player who leaves is called off, and the last
+        // one standing wins
+        let events = room.leave(&others[0], true, 1002).unwrap();
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::VoteFailed { .. })));
+        let events = room.leave(&others[1], true, 1003).unwrap();
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::GameOver { winner_id, .. } if *winner_id == others[2])));
+        assert!(room.leave(&others[2], false, 1004).is_ok());
+    }
+
+    #[test]
+    fn test_forfeit_gives_up_the_seat_under_hand_to_bot() {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player { bot: Some(crate::game::bot::BotStrategy::Cautious), ..Player::new("b", "B") });
+        room.start_game();
+        room.game_state.as_mut().unwrap().rules.estate_policy = crate::game::rules::EstatePolicy::HandToBot;
+
+        let events = room.leave("a", true, 1000).unwrap();
+        assert!(!events.iter().any(|e| matches!(e, ServerMessage::PlayerJoined { .. })));
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::GameOver { winner_id, .. } if winner_id == "b")));
+        assert_eq!(room.players.len(), 1);
+        let state = room.game_state.as_ref().unwrap();
+        assert_eq!(state.phase, GamePhase::GameOver);
+        assert_eq!(state.players.len(), 1);
+    }
+}
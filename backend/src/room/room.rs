@@ -1,51 +1,483 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tokio::sync::broadcast;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tokio::sync::mpsc;
 use crate::game::state::{GameState, PlayerState, GamePhase};
-use crate::room::player::Player;
+use crate::game::board::Board;
+use crate::game::bot::{self, BotStrategy};
+use crate::game::cards::DeckSet;
+use crate::room::player::{Player, Presence, Spectator};
+use crate::room::chat::ChatMessage;
+use crate::room::settings::RoomSettings;
+use crate::room::timer::TurnClock;
+use crate::game::command::GameCommand;
+use crate::game::log::{LogEntry, LogRecord};
+use crate::game::rng::GameRng;
+use crate::game::rules::EstatePolicy;
 use crate::ws::messages::ServerMessage;
 
-#[derive(Debug, Clone, Serialize)]
+/// Seconds a kick vote stays open
+pub const VOTE_DURATION_SECS: u64 = 30;
+/// Seconds before a failed vote's initiator can start another one, and before
+/// its target can be voted on again
+pub const VOTE_COOLDOWN_SECS: u64 = 60;
+/// Seconds a finished game stays open for its players before the room closes
+pub const GAME_OVER_GRACE_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Room {
     pub id: String,
     pub players: HashMap<String, Player>,
     pub game_state: Option<GameState>,
+    /// Player or spectator id -> outbound channels of their open sockets
     #[serde(skip)]
-    pub tx: broadcast::Sender<ServerMessage>,
+    pub outboxes: HashMap<String, Vec<mpsc::UnboundedSender<ServerMessage>>>,
     pub vote_state: Option<VoteState>,
+    /// Player id -> Unix time until which they cannot start a kick vote
+    pub initiator_cooldowns: HashMap<String, u64>,
+    /// Player id -> Unix time until which no kick vote can target them
+    pub target_cooldowns: HashMap<String, u64>,
+    pub board: Board,
+    pub decks: DeckSet,
+    pub host_id: Option<String>,
+    pub settings: RoomSettings,
+    /// Debug mode: seed of the next game, random when unset
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(skip)]
+    pub turn_clock: Option<TurnClock>,
+    /// Player id -> number of open sockets
+    #[serde(skip)]
+    pub connections: HashMap<String, usize>,
+    /// Latest chat messages, oldest first
+    #[serde(default)]
+    pub chat: VecDeque<ChatMessage>,
+    /// Id of the next chat message
+    #[serde(default)]
+    pub chat_seq: u64,
+    /// Players whose chat messages are refused
+    #[serde(default)]
+    pub muted: HashSet<String>,
+    /// Watching sockets; they leave with their socket, so are not saved
+    #[serde(skip)]
+    pub spectators: HashMap<String, Spectator>,
+    /// Unix time at which the room's game ended
+    #[serde(default)]
+    pub game_over_at: Option<u64>,
+    /// Sequence number of the next game log entry
+    pub log_seq: u64,
+    /// Log records not yet written to the room store
+    #[serde(skip)]
+    pub unsaved_log: Vec<LogRecord>,
+}
+
+/// What a passed vote does to its target
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum VoteKind {
+    /// Removes the player from the room
+    #[default]
+    Kick,
+    /// Stops the player's chat messages
+    Mute,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoteState {
+    #[serde(default)]
+    pub kind: VoteKind,
     pub initiator_id: String,
     pub target_player_id: String,
-    pub votes_for: std::collections::HashSet<String>,
-    pub votes_against: std::collections::HashSet<String>,
+    pub votes_for: HashSet<String>,
+    pub votes_against: HashSet<String>,
     pub end_time: u64, // Unix timestamp in seconds
 }
 
 impl Room {
-    pub fn new(id: String) -> Self {
-        let (tx, _rx) = broadcast::channel(100);
+    pub fn new(id: String, board: Board, decks: DeckSet) -> Self {
         Self {
             id,
             players: HashMap::new(),
             game_state: None,
-            tx,
+            outboxes: HashMap::new(),
             vote_state: None,
+            initiator_cooldowns: HashMap::new(),
+            target_cooldowns: HashMap::new(),
+            settings: RoomSettings {
+                board_id: board.id.clone(),
+                ..RoomSettings::default()
+            },
+            board,
+            decks,
+            host_id: None,
+            seed: None,
+            turn_clock: None,
+            connections: HashMap::new(),
+            chat: VecDeque::new(),
+            chat_seq: 0,
+            muted: HashSet::new(),
+            spectators: HashMap::new(),
+            game_over_at: None,
+            log_seq: 0,
+            unsaved_log: Vec::new(),
         }
     }
 
     pub fn add_player(&mut self, player: Player) {
+        // First player in becomes the host
+        if self.host_id.is_none() {
+            self.host_id = Some(player.id.clone());
+        }
         self.players.insert(player.id.clone(), player);
     }
 
     pub fn remove_player(&mut self, player_id: &str) {
         self.players.remove(player_id);
+
+        // Hand the host role to someone still in the room
+        if self.host_id.as_deref() == Some(player_id) {
+            let mut remaining: Vec<&String> = self.players.keys().collect();
+            remaining.sort();
+            self.host_id = remaining.first().map(|id| id.to_string());
+        }
+    }
+
+    pub fn add_spectator(&mut self, name: String) -> Spectator {
+        let spectator = Spectator { id: uuid::Uuid::new_v4().to_string(), name };
+        self.spectators.insert(spectator.id.clone(), spectator.clone());
+        spectator
+    }
+
+    /// Spectators sorted by name, as shown to the room
+    pub fn spectator_list(&self) -> Vec<Spectator> {
+        let mut spectators: Vec<Spectator> = self.spectators.values().cloned().collect();
+        spectators.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        spectators
+    }
+
+    /// Seats a bot with the given strategy. Only the host can add bots, and
+    /// only before the game starts.
+    pub fn add_bot(&mut self, player_id: &str, strategy: BotStrategy) -> Result<Player, String> {
+        self.check_host(player_id)?;
+        if self.game_state.is_some() {
+            return Err("Bots can only be added before the game starts".to_string());
+        }
+
+        let number = self.players.values().filter(|p| p.bot.is_some()).count() + 1;
+        let player = Player {
+            bot: Some(strategy),
+            ..Player::new(uuid::Uuid::new_v4().to_string(), format!("Bot {} ({:?})", number, strategy))
+        };
+        self.add_player(player.clone());
+        Ok(player)
+    }
+
+    /// Casts the bots' kick votes and plays one command for every bot or
+    /// stand-in the game is waiting on, through the same commands players send
+    pub fn play_bots(&mut self, now: u64) -> Vec<ServerMessage> {
+        let mut seats: Vec<(String, BotStrategy)> = self.players.values()
+            .filter_map(|p| match (p.bot, &p.presence) {
+                (Some(strategy), _) => Some((p.id.clone(), strategy)),
+                (None, Presence::StandIn { .. }) => Some((p.id.clone(), BotStrategy::Cautious)),
+                _ => None,
+            })
+            .collect();
+        if seats.is_empty() {
+            return Vec::new();
+        }
+        seats.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut events = Vec::new();
+        if let Some(vote_state) = &self.vote_state {
+            let target_id = vote_state.target_player_id.clone();
+            let target_is_bot = self.players.get(&target_id).is_some_and(|p| p.bot.is_some());
+            let humans: Vec<&String> = self.players.values()
+                .filter(|p| p.bot.is_none() && p.id != target_id)
+                .map(|p| &p.id)
+                .collect();
+            let humans_for = humans.iter().filter(|id| vote_state.votes_for.contains(**id)).count();
+
+            if bot::backs_vote(target_is_bot, humans_for, humans.len()) {
+                // Stand-ins leave kick votes to the players they sit in for
+                let voters: Vec<String> = self.players.values()
+                    .filter(|p| p.bot.is_some() && p.id != target_id && !vote_state.votes_for.contains(&p.id))
+                    .map(|p| p.id.clone())
+                    .collect();
+                for voter in voters {
+                    if self.vote_state.is_none() {
+                        break;
+                    }
+                    events.extend(self.cast_vote(&voter, true, now));
+                }
+            }
+        }
+
+        for (bot_id, strategy) in seats {
+            let Some(command) = self.game_state.as_ref().and_then(|state| strategy.next_command(state, &bot_id)) else {
+                continue;
+            };
+            match self.dispatch(Some(&bot_id), command.clone(), now) {
+                Ok(bot_events) => events.extend(bot_events),
+                Err(e) => {
+                    tracing::warn!("Bot {} in room {} sent {:?}: {}", bot_id, self.id, command, e);
+                    // A trade that no longer holds is turned down rather than accepted again every tick
+                    if let GameCommand::AcceptTrade { trade_id } = command {
+                        let reject = GameCommand::RejectTrade { trade_id };
+                        events.extend(self.dispatch(Some(&bot_id), reject, now).unwrap_or_default());
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    pub fn check_host(&self, player_id: &str) -> Result<(), String> {
+        if self.host_id.as_deref() == Some(player_id) {
+            Ok(())
+        } else {
+            Err("Only the host can do that".to_string())
+        }
+    }
+
+    /// Replaces the lobby settings. `board` and `decks` are the edition
+    /// resolved from `settings.board_id`.
+    pub fn update_settings(&mut self, player_id: &str, settings: RoomSettings, board: Board, decks: DeckSet) -> Result<(), String> {
+        self.check_host(player_id)?;
+        if self.game_state.is_some() {
+            return Err("Settings cannot be changed after the game has started".to_string());
+        }
+        settings.house_rules.validate()?;
+
+        self.settings = settings;
+        self.board = board;
+        self.decks = decks;
+        Ok(())
+    }
+
+    pub fn start_vote(&mut self, initiator_id: &str, target_player_id: &str, kind: VoteKind, now: u64) -> Result<Vec<ServerMessage>, String> {
+        if self.vote_state.is_some() {
+            return Err("Vote already in progress".to_string());
+        }
+        if !self.players.contains_key(target_player_id) {
+            return Err("Player not found".to_string());
+        }
+        if let Some(until) = self.initiator_cooldowns.get(initiator_id).filter(|&&until| until > now) {
+            return Err(format!("You can start another vote in {} seconds", until - now));
+        }
+        if let Some(until) = self.target_cooldowns.get(target_player_id).filter(|&&until| until > now) {
+            return Err(format!("This player can be voted on again in {} seconds", until - now));
+        }
+
+        let end_time = now + VOTE_DURATION_SECS;
+        let mut votes_for = HashSet::new();
+        votes_for.insert(initiator_id.to_string()); // Initiator automatically votes yes
+
+        self.vote_state = Some(VoteState {
+            kind,
+            initiator_id: initiator_id.to_string(),
+            target_player_id: target_player_id.to_string(),
+            votes_for,
+            votes_against: HashSet::new(),
+            end_time,
+        });
+
+        Ok(vec![
+            ServerMessage::VoteStarted {
+                kind,
+                initiator_id: initiator_id.to_string(),
+                target_player_id: target_player_id.to_string(),
+                end_time,
+            },
+            ServerMessage::VoteUpdate {
+                votes_for: 1,
+                votes_against: 0,
+                required: self.votes_required(),
+            },
+        ])
+    }
+
+    pub fn cast_vote(&mut self, player_id: &str, vote: bool, now: u64) -> Vec<ServerMessage> {
+        let required = self.votes_required();
+        let Some(vote_state) = &mut self.vote_state else {
+            return Vec::new();
+        };
+        if vote {
+            vote_state.votes_for.insert(player_id.to_string());
+            vote_state.votes_against.remove(player_id);
+        } else {
+            vote_state.votes_against.insert(player_id.to_string());
+            vote_state.votes_for.remove(player_id);
+        }
+
+        let votes_for = vote_state.votes_for.len();
+        let votes_against = vote_state.votes_against.len();
+        let mut events = vec![ServerMessage::VoteUpdate { votes_for, votes_against, required }];
+
+        // Everyone but the target counts towards the majority; the target may
+        // still vote against to help themselves
+        let remaining_players = self.players.len().saturating_sub(1);
+        if votes_for >= required {
+            events.extend(self.finish_vote(true, now));
+        } else if votes_against > remaining_players.saturating_sub(required) {
+            // Vote failed (impossible to reach majority)
+            events.extend(self.finish_vote(false, now));
+        }
+        events
+    }
+
+    /// Closes a kick vote whose time ran out
+    pub fn resolve_expired_vote(&mut self, now: u64) -> Vec<ServerMessage> {
+        match &self.vote_state {
+            Some(vote_state) if now >= vote_state.end_time => {
+                let passed = vote_state.votes_for.len() >= self.votes_required();
+                tracing::info!("Kick vote in room {} expired, passed={}", self.id, passed);
+                self.finish_vote(passed, now)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Majority of the players other than the target
+    fn votes_required(&self) -> usize {
+        self.players.len().saturating_sub(1) / 2 + 1
+    }
+
+    fn finish_vote(&mut self, passed: bool, now: u64) -> Vec<ServerMessage> {
+        let Some(vote_state) = self.vote_state.take() else {
+            return Vec::new();
+        };
+        let target_id = vote_state.target_player_id;
+
+        if !passed {
+            self.initiator_cooldowns.insert(vote_state.initiator_id, now + VOTE_COOLDOWN_SECS);
+            self.target_cooldowns.insert(target_id, now + VOTE_COOLDOWN_SECS);
+            let reason = if now >= vote_state.end_time { "Vote expired" } else { "Not enough votes" };
+            return vec![ServerMessage::VoteFailed { reason: reason.to_string() }];
+        }
+
+        if vote_state.kind == VoteKind::Mute {
+            self.muted.insert(target_id.clone());
+            return vec![ServerMessage::PlayerMuted { player_id: target_id, muted: true }];
+        }
+
+        // Remove from game state, then from the room
+        let mut events = self.remove_from_game(&target_id, now);
+        self.remove_player(&target_id);
+        events.push(ServerMessage::PlayerKicked { player_id: target_id });
+        events
+    }
+
+    /// Takes a player out of the room at their own request. A running game can
+    /// only be left by forfeiting it, which gives up the seat: no bot takes it
+    /// over and the estates go back to the bank, or to auction. Spectators
+    /// simply leave.
+    pub fn leave(&mut self, player_id: &str, forfeit: bool, now: u64) -> Result<Vec<ServerMessage>, String> {
+        if !forfeit && self.spectators.remove(player_id).is_some() {
+            self.outboxes.remove(player_id);
+            return Ok(vec![ServerMessage::SpectatorsUpdated { spectators: self.spectator_list() }]);
+        }
+        if !self.players.contains_key(player_id) {
+            return Err("Player not found".to_string());
+        }
+        let in_game = self.game_state.as_ref().is_some_and(|state| {
+            state.phase != GamePhase::GameOver && state.players.iter().any(|p| p.id == player_id)
+        });
+        match (forfeit, in_game) {
+            (false, true) => return Err("Forfeit to leave a running game".to_string()),
+            (true, false) => return Err("No game to forfeit".to_string()),
+            _ => {}
+        }
+
+        let mut events = Vec::new();
+        // A vote on or by the player is called off; their ballot in any other is dropped
+        let mut ballot_dropped = false;
+        if let Some(vote_state) = &mut self.vote_state {
+            if vote_state.initiator_id == player_id || vote_state.target_player_id == player_id {
+                self.vote_state = None;
+                events.push(ServerMessage::VoteFailed { reason: "Player left".to_string() });
+            } else {
+                ballot_dropped = vote_state.votes_for.remove(player_id) | vote_state.votes_against.remove(player_id);
+            }
+        }
+
+        if forfeit {
+            let command = GameCommand::RemovePlayer { player_id: player_id.to_string() };
+            events.extend(self.dispatch(None, command, now).unwrap_or_default());
+        }
+        self.remove_player(player_id);
+        self.connections.remove(player_id);
+        self.muted.remove(player_id);
+        self.outboxes.remove(player_id);
+
+        if let Some(vote_state) = self.vote_state.as_ref().filter(|_| ballot_dropped) {
+            events.push(ServerMessage::VoteUpdate {
+                votes_for: vote_state.votes_for.len(),
+                votes_against: vote_state.votes_against.len(),
+                required: self.votes_required(),
+            });
+        }
+        tracing::info!("Player {} left room {}, forfeited={}", player_id, self.id, forfeit);
+        events.push(ServerMessage::PlayerLeft { player_id: player_id.to_string(), forfeited: forfeit });
+        Ok(events)
+    }
+
+    /// Whether the room can be closed: its game ended `GAME_OVER_GRACE_SECS`
+    /// ago, or its lobby was abandoned
+    pub fn is_finished(&self, now: u64) -> bool {
+        self.game_over_at.is_some_and(|at| now >= at + GAME_OVER_GRACE_SECS) || self.is_abandoned(now)
+    }
+
+    /// Settles the estates of a player kicked from a running game by the room's
+    /// `EstatePolicy`: a bot joins to play their seat, or the game drops them
+    pub fn remove_from_game(&mut self, player_id: &str, now: u64) -> Vec<ServerMessage> {
+        let Some(state) = &self.game_state else {
+            return Vec::new();
+        };
+        if state.rules.estate_policy == EstatePolicy::HandToBot && state.phase != GamePhase::GameOver {
+            let name = self.players.get(player_id).map(|p| p.name.clone()).unwrap_or_default();
+            let bot = Player {
+                bot: Some(BotStrategy::Cautious),
+                ..Player::new(uuid::Uuid::new_v4().to_string(), format!("Bot ({})", name))
+            };
+            let command = GameCommand::ReplaceWithBot {
+                player_id: player_id.to_string(),
+                bot_id: bot.id.clone(),
+                bot_name: bot.name.clone(),
+            };
+            if let Ok(events) = self.dispatch(None, command, now) {
+                self.add_player(bot.clone());
+                return std::iter::once(ServerMessage::PlayerJoined { player: bot }).chain(events).collect();
+            }
+        }
+        let command = GameCommand::RemovePlayer { player_id: player_id.to_string() };
+        self.dispatch(None, command, now).unwrap_or_default()
+    }
+
+    /// Applies a command to the running game and records it in the game log
+    pub fn dispatch(&mut self, actor: Option<&str>, command: GameCommand, now: u64) -> Result<Vec<ServerMessage>, String> {
+        let state = self.game_state.as_mut().ok_or("Game has not started")?;
+        let events = state.apply(actor, &command, now)?;
+        if state.phase == GamePhase::GameOver && self.game_over_at.is_none() {
+            self.game_over_at = Some(now);
+        }
+
+        let entry = LogEntry::new(self.log_seq, now, actor, command, &events);
+        self.log_seq += 1;
+        self.unsaved_log.push(LogRecord::Command(entry));
+        Ok(events)
+    }
+
+    /// Starts the game at the host's request, unless one is already running
+    pub fn start_game_by(&mut self, player_id: &str) -> Result<(), String> {
+        self.check_host(player_id)?;
+        if self.game_state.as_ref().is_some_and(|state| state.phase != GamePhase::GameOver) {
+            return Err("The game has already started".to_string());
+        }
+        self.start_game();
+        Ok(())
     }
 
     pub fn start_game(&mut self) {
-        let mut game_state = GameState::new();
+        let rng = self.seed.map(GameRng::new).unwrap_or_else(GameRng::from_entropy);
+        let mut game_state = GameState::with_board(self.board.clone(), &self.decks, self.settings.house_rules.clone(), rng);
         // Convert room players to game players
         // We need a deterministic order, so let's sort by ID or something, or just take values
         // For now, just values is fine but random order. Let's sort keys to be stable.
@@ -57,7 +489,7 @@ impl Room {
                 game_state.players.push(PlayerState {
                     id: p.id.clone(),
                     name: p.name.clone(),
-                    money: 1_500_000,
+                    money: self.settings.house_rules.starting_money,
                     position: 0,
                     color: match i % 4 {
                         0 => "red".to_string(),
@@ -74,6 +506,69 @@ impl Room {
         }
         
         game_state.phase = GamePhase::Rolling;
+        self.log_seq = 0;
+        self.unsaved_log.push(LogRecord::Started {
+            seed: game_state.rng.seed(),
+            initial_state: Box::new(game_state.clone()),
+            board: Box::new(self.board.clone()),
+        });
         self.game_state = Some(game_state);
+        self.game_over_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lobby() -> Room {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room
+    }
+
+    #[test]
+    fn test_only_host_starts_game_once() {
+        let mut room = lobby();
+        assert!(room.start_game_by("b").is_err());
+        assert!(room.game_state.is_none());
+
+        room.start_game_by("a").unwrap();
+        let first = room.game_state.as_ref().unwrap().acting_player_id().unwrap().to_string();
+        room.dispatch(Some(&first), GameCommand::RollDice, 1000).unwrap();
+        // A running game is never restarted
+        assert!(room.start_game_by("a").is_err());
+        assert!(room.game_state.as_ref().unwrap().last_dice_roll.is_some());
+        assert_eq!(room.log_seq, 1);
+
+        room.game_state.as_mut().unwrap().phase = GamePhase::GameOver;
+        assert!(room.start_game_by("a").is_ok());
+        assert!(room.game_state.as_ref().unwrap().last_dice_roll.is_none());
+    }
+
+    #[test]
+    fn test_kicked_players_seat_goes_to_bot() {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        for id in ["a", "b", "c"] {
+            room.add_player(Player::new(id, id.to_uppercase()));
+        }
+        room.settings.house_rules.estate_policy = EstatePolicy::HandToBot;
+        room.start_game();
+        room.game_state.as_mut().unwrap().properties[0].owner_id = Some("b".to_string());
+
+        room.start_vote("a", "b", VoteKind::Kick, 1000).unwrap();
+        let events = room.cast_vote("c", true, 1001);
+        let Some(ServerMessage::EstateSettled { bot_id: Some(bot_id), property_ids, .. }) =
+            events.iter().find(|e| matches!(e, ServerMessage::EstateSettled { .. }))
+        else {
+            panic!("no estate settlement in {:?}", events);
+        };
+        assert_eq!(property_ids.len(), 1);
+        assert!(!room.players.contains_key("b"));
+        assert!(room.players[bot_id].bot.is_some());
+        let state = room.game_state.as_ref().unwrap();
+        assert!(state.players.iter().any(|p| &p.id == bot_id));
+        assert_eq!(state.properties[0].owner_id.as_ref(), Some(bot_id));
     }
 }
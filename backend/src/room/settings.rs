@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use crate::game::board::DEFAULT_BOARD_ID;
+use crate::game::rules::HouseRules;
+
+/// Lobby configuration edited by the host before the game starts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomSettings {
+    pub board_id: String,
+    pub house_rules: HouseRules,
+}
+
+impl Default for RoomSettings {
+    fn default() -> Self {
+        Self {
+            board_id: DEFAULT_BOARD_ID.to_string(),
+            house_rules: HouseRules::default(),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use crate::game::log::{GameLog, LogRecord};
+use crate::room::room::Room;
+
+/// Where room snapshots are kept between server restarts
+pub trait RoomStore: Send + Sync {
+    fn save(&self, room: &Room) -> Result<(), String>;
+    /// Drops the room's snapshot. Its game log is kept for replays.
+    fn delete(&self, room_id: &str) -> Result<(), String>;
+    fn load_all(&self) -> Result<Vec<Room>, String>;
+    /// Appends records to the room's game log
+    fn append_log(&self, room_id: &str, records: &[LogRecord]) -> Result<(), String>;
+    /// Reads back the log of the room's latest game
+    fn load_log(&self, room_id: &str) -> Result<GameLog, String>;
+}
+
+/// Keeps snapshots in memory only; rooms do not survive a restart
+#[derive(Default)]
+pub struct MemoryStore {
+    rooms: Mutex<HashMap<String, Room>>,
+    logs: Mutex<HashMap<String, Vec<LogRecord>>>,
+}
+
+impl RoomStore for MemoryStore {
+    fn save(&self, room: &Room) -> Result<(), String> {
+        self.rooms.lock().unwrap().insert(room.id.clone(), room.clone());
+        Ok(())
+    }
+
+    fn delete(&self, room_id: &str) -> Result<(), String> {
+        self.rooms.lock().unwrap().remove(room_id);
+        Ok(())
+    }
+
+    fn load_all(&self) -> Result<Vec<Room>, String> {
+        Ok(self.rooms.lock().unwrap().values().cloned().collect())
+    }
+
+    fn append_log(&self, room_id: &str, records: &[LogRecord]) -> Result<(), String> {
+        self.logs.lock().unwrap().entry(room_id.to_string()).or_default().extend_from_slice(records);
+        Ok(())
+    }
+
+    fn load_log(&self, room_id: &str) -> Result<GameLog, String> {
+        let records = self.logs.lock().unwrap().get(room_id).cloned().unwrap_or_default();
+        GameLog::from_records(records)
+    }
+}
+
+/// Writes one `<room_id>.json` snapshot per room into a directory, next to
+/// an append-only `<room_id>.log.jsonl` game log
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, String> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, room_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", room_id))
+    }
+
+    fn log_path(&self, room_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.log.jsonl", room_id))
+    }
+
+    /// Deletes the game logs of closed rooms that have not been written to
+    /// for `max_age`. Returns the number of logs deleted.
+    pub fn prune_logs(&self, max_age: Duration) -> usize {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return 0;
+        };
+        let mut pruned = 0;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(room_id) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".log.jsonl")) else {
+                continue;
+            };
+            let stale = entry.metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age >= max_age));
+            if stale && !self.path(room_id).exists() {
+                match std::fs::remove_file(&path) {
+                    Ok(()) => pruned += 1,
+                    Err(e) => tracing::warn!("Cannot delete game log {}: {}", path.display(), e),
+                }
+            }
+        }
+        pruned
+    }
+}
+
+impl RoomStore for FileStore {
+    fn save(&self, room: &Room) -> Result<(), String> {
+        let json = serde_json::to_string(room).map_err(|e| format!("Cannot serialize room {}: {}", room.id, e))?;
+        // Write to a temporary file first so a crash never leaves a half-written snapshot
+        let tmp = self.dir.join(format!("{}.json.tmp", room.id));
+        std::fs::write(&tmp, json).map_err(|e| format!("Cannot write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, self.path(&room.id)).map_err(|e| format!("Cannot save room {}: {}", room.id, e))
+    }
+
+    fn delete(&self, room_id: &str) -> Result<(), String> {
+        let path = self.path(room_id);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Cannot delete {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Loads every snapshot in the directory. Unreadable files are skipped with a warning.
+    fn load_all(&self) -> Result<Vec<Room>, String> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| format!("Cannot read {}: {}", self.dir.display(), e))?;
+
+        let mut rooms = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let room = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| serde_json::from_str::<Room>(&json).map_err(|e| e.to_string()));
+            match room {
+                Ok(room) => rooms.push(room),
+                Err(e) => tracing::warn!("Skipping room snapshot {}: {}", path.display(), e),
+            }
+        }
+        Ok(rooms)
+    }
+
+    fn append_log(&self, room_id: &str, records: &[LogRecord]) -> Result<(), String> {
+        let path = self.log_path(room_id);
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)
+            .map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+        let mut lines = String::new();
+        for record in records {
+            lines.push_str(&serde_json::to_string(record).map_err(|e| format!("Cannot serialize log record: {}", e))?);
+            lines.push('\n');
+        }
+        file.write_all(lines.as_bytes()).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    fn load_log(&self, room_id: &str) -> Result<GameLog, String> {
+        let path = self.log_path(room_id);
+        let text = std::fs::read_to_string(&path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let records = text.lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str::<LogRecord>(line).map_err(|e| format!("Corrupt log line in {}: {}", path.display(), e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        GameLog::from_records(records)
+    }
+}
+
+enum StoreOp {
+    Save(String),
+    Delete(String),
+}
+
+/// Saves rooms on a thread of its own, in the order asked, so that rooms are
+/// never locked while the store writes
+#[derive(Clone)]
+pub struct StoreWriter {
+    tx: mpsc::Sender<StoreOp>,
+}
+
+impl StoreWriter {
+    pub fn spawn(store: Arc<dyn RoomStore>, rooms: Arc<DashMap<String, Room>>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for op in rx {
+                match op {
+                    StoreOp::Save(room_id) => save_room(store.as_ref(), &rooms, &room_id),
+                    StoreOp::Delete(room_id) => {
+                        if let Err(e) = store.delete(&room_id) {
+                            tracing::error!("Failed to delete room {}: {}", room_id, e);
+                        }
+                    }
+                }
+            }
+        });
+        Self { tx }
+    }
+
+    /// Writes the room's new game log records and a snapshot of it
+    pub fn save(&self, room_id: &str) {
+        let _ = self.tx.send(StoreOp::Save(room_id.to_string()));
+    }
+
+    pub fn delete(&self, room_id: &str) {
+        let _ = self.tx.send(StoreOp::Delete(room_id.to_string()));
+    }
+}
+
+fn save_room(store: &dyn RoomStore, rooms: &DashMap<String, Room>, room_id: &str) {
+    // Only hold the room for as long as it takes to copy it
+    let Some((records, snapshot)) = rooms.get_mut(room_id)
+        .map(|mut room| (std::mem::take(&mut room.unsaved_log), room.clone()))
+    else {
+        return;
+    };
+    if !records.is_empty() {
+        if let Err(e) = store.append_log(room_id, &records) {
+            tracing::error!("Failed to append game log of room {}: {}", room_id, e);
+            // Retried with the next save, ahead of anything logged since
+            if let Some(mut room) = rooms.get_mut(room_id) {
+                room.unsaved_log.splice(0..0, records);
+            }
+        }
+    }
+    if let Err(e) = store.save(&snapshot) {
+        tracing::error!("Failed to persist room {}: {}", room_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::cards::DeckSet;
+    use crate::room::player::Player;
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("room-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(&dir).unwrap();
+
+        let mut room = Room::new("ABC123".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room.start_game();
+        room.game_state.as_mut().unwrap().players[0].money = 1234;
+        store.save(&room).unwrap();
+        // The board is saved once, with the room
+        let snapshot = serde_json::to_value(&room).unwrap();
+        assert!(snapshot["board"].is_object());
+        assert!(snapshot["game_state"].get("board").is_none());
+
+        let rooms = store.load_all().unwrap();
+        assert_eq!(rooms.len(), 1);
+        assert_eq!(rooms[0].host_id, room.host_id);
+        assert_eq!(rooms[0].game_state.as_ref().unwrap().players[0].money, 1234);
+
+        store.append_log("ABC123", &std::mem::take(&mut room.unsaved_log)).unwrap();
+        store.delete("ABC123").unwrap();
+        assert!(store.load_all().unwrap().is_empty());
+        assert!(store.load_log("ABC123").is_ok());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_game_log_replays_to_current_state() {
+        let store = MemoryStore::default();
+        let mut room = Room::new("ABC123".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room.start_game();
+
+        let first = room.game_state.as_ref().unwrap().acting_player_id().unwrap().to_string();
+        room.dispatch(Some(&first), crate::game::command::GameCommand::RollDice, 1000).unwrap();
+        store.append_log("ABC123", &std::mem::take(&mut room.unsaved_log)).unwrap();
+
+        let log = store.load_log("ABC123").unwrap();
+        assert_eq!(log.entries.len(), 1);
+        let replayed = log.replay(None).unwrap();
+        let current = room.game_state.as_ref().unwrap();
+        assert_eq!(replayed.last_dice_roll, current.last_dice_roll);
+        assert_eq!(replayed.players[0].position, current.players[0].position);
+    }
+
+    #[test]
+    fn test_closed_rooms_can_be_replayed() {
+        let dir = std::env::temp_dir().join(format!("room-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(&dir).unwrap();
+        let board = Board { id: "custom".to_string(), ..Board::default() };
+        let mut room = Room::new("ABC123".to_string(), board, DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room.start_game();
+        let first = room.game_state.as_ref().unwrap().acting_player_id().unwrap().to_string();
+        room.dispatch(Some(&first), crate::game::command::GameCommand::RollDice, 1000).unwrap();
+        store.append_log("ABC123", &std::mem::take(&mut room.unsaved_log)).unwrap();
+        store.save(&room).unwrap();
+
+        store.delete("ABC123").unwrap();
+        let replayed = store.load_log("ABC123").unwrap().replay(None).unwrap();
+        assert_eq!(replayed.board.id, "custom");
+        assert_eq!(replayed.players[0].position, room.game_state.as_ref().unwrap().players[0].position);
+
+        // Logs of closed rooms are pruned once they are old enough
+        assert_eq!(store.prune_logs(Duration::from_secs(3600)), 0);
+        assert_eq!(store.prune_logs(Duration::ZERO), 1);
+        assert!(store.load_log("ABC123").is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_log_append_keeps_records() {
+        let dir = std::env::temp_dir().join(format!("room-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(&dir).unwrap();
+        let rooms = DashMap::new();
+        let mut room = Room::new("ABC123".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room.start_game();
+        rooms.insert(room.id.clone(), room);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        save_room(&store, &rooms, "ABC123");
+        assert_eq!(rooms.get("ABC123").unwrap().unsaved_log.len(), 1);
+
+        std::fs::create_dir_all(&dir).unwrap();
+        save_room(&store, &rooms, "ABC123");
+        assert!(rooms.get("ABC123").unwrap().unsaved_log.is_empty());
+        assert!(store.load_log("ABC123").is_ok());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
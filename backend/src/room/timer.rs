@@ -0,0 +1,273 @@
+use serde::Serialize;
+use std::time::Duration;
+use crate::game::command::GameCommand;
+use crate::game::state::{GamePhase, unix_now};
+use crate::room::manager::RoomManager;
+use crate::room::room::Room;
+use crate::ws::messages::ServerMessage;
+
+/// Seconds before the deadline at which the acting player is warned
+pub const TURN_WARNING_SECS: u64 = 15;
+/// Seconds between the steps played for a player whose turn has timed out
+pub const TURN_AUTO_STEP_SECS: u64 = 2;
+
+/// Deadline for the player the game is currently waiting on
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnClock {
+    pub player_id: String,
+    pub phase: GamePhase,
+    pub last_dice_roll: Option<(u8, u8)>,
+    pub deadline: u64, // Unix timestamp in seconds
+    pub warned: bool,
+    /// The player already timed out this turn, so the rest of it is played
+    /// every `TURN_AUTO_STEP_SECS`
+    pub auto: bool,
+}
+
+impl TurnClock {
+    /// Whether the clock still belongs to the same step of the game. Any
+    /// change of player, phase or dice means the player acted.
+    fn matches(&self, player_id: &str, phase: &GamePhase, last_dice_roll: Option<(u8, u8)>) -> bool {
+        self.player_id == player_id && &self.phase == phase && self.last_dice_roll == last_dice_roll
+    }
+}
+
+impl Room {
+    /// Advances everything that runs on the clock: kick votes, auction and
+    /// trade expiry, auctions of bankrupt estates, stand-ins for away players,
+    /// bot moves and the turn timer. Returns the events to broadcast.
+    pub fn tick(&mut self, now: u64) -> Vec<ServerMessage> {
+        let mut events = self.resolve_expired_vote(now);
+        if self.game_state.as_ref().and_then(|s| s.auction.as_ref()).is_some_and(|a| now >= a.ends_at) {
+            events.extend(self.dispatch(None, GameCommand::AuctionExpired, now).unwrap_or_default());
+        }
+        let trades_expired = self.game_state.as_ref()
+            .is_some_and(|s| s.active_trades.values().any(|t| t.expires_at.is_some_and(|at| now >= at)));
+        if trades_expired {
+            events.extend(self.dispatch(None, GameCommand::TradesExpired, now).unwrap_or_default());
+        }
+        if self.game_state.as_ref().is_some_and(|s| s.bank_auction_due()) {
+            events.extend(self.dispatch(None, GameCommand::AuctionBankEstate, now).unwrap_or_default());
+        }
+        events.extend(self.take_over_away_players(now));
+        events.extend(self.play_bots(now));
+        let Some(state) = &self.game_state else {
+            return events;
+        };
+
+        let timeout = state.rules.turn_timeout_secs;
+        let timed_phase = matches!(state.phase, GamePhase::Rolling | GamePhase::EndTurn | GamePhase::DebtResolution);
+        let Some(player_id) = state.acting_player_id().filter(|_| timeout > 0 && timed_phase).map(|id| id.to_string()) else {
+            // A timed-out turn stays on the short clock through auctions
+            if !self.turn_clock.as_ref().is_some_and(|c| c.auto) {
+                self.turn_clock = None;
+            }
+            return events;
+        };
+
+        let current = self.turn_clock.as_ref().is_some_and(|c| c.matches(&player_id, &state.phase, state.last_dice_roll));
+        if !current {
+            let auto = self.turn_clock.as_ref().is_some_and(|c| c.auto && c.player_id == player_id);
+            let clock = TurnClock {
+                player_id: player_id.clone(),
+                phase: state.phase.clone(),
+                last_dice_roll: state.last_dice_roll,
+                deadline: now + if auto { TURN_AUTO_STEP_SECS } else { timeout },
+                warned: auto,
+                auto,
+            };
+            events.push(ServerMessage::TurnTimerStarted {
+                player_id,
+                phase: clock.phase.clone(),
+                deadline: clock.deadline,
+            });
+            self.turn_clock = Some(clock);
+            return events;
+        }
+        let Some(clock) = &mut self.turn_clock else {
+            return events;
+        };
+
+        if now >= clock.deadline {
+            tracing::info!("Turn timer expired for {} in room {} ({:?})", clock.player_id, self.id, clock.phase);
+            clock.auto = true;
+            clock.deadline = now + TURN_AUTO_STEP_SECS;
+            events.extend(self.dispatch(None, GameCommand::TurnTimeout, now).unwrap_or_default());
+        } else if !clock.warned && now + TURN_WARNING_SECS >= clock.deadline {
+            clock.warned = true;
+            events.push(ServerMessage::TurnTimerWarning {
+                player_id: clock.player_id.clone(),
+                seconds_left: clock.deadline - now,
+            });
+        }
+        events
+    }
+}
+
+/// Runs the clock of one room once per second until the room is gone
+pub fn spawn_room_ticker(room_manager: RoomManager, room_id: String) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+
+            let (events, finished) = {
+                let Some(mut room) = room_manager.rooms.get_mut(&room_id) else {
+                    tracing::info!("Room {} closed, stopping its timer", room_id);
+                    return;
+                };
+                let now = unix_now();
+                let events = room.tick(now);
+                room.send_all(events.clone());
+                (events, room.is_finished(now))
+            };
+            if finished {
+                room_manager.close_room(&room_id);
+            } else if !events.is_empty() {
+                room_manager.persist(&room_id);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::cards::DeckSet;
+    use crate::room::player::Player;
+    use crate::room::room::VoteKind;
+
+    fn started_room() -> Room {
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        room.add_player(Player::new("b", "B"));
+        room.start_game();
+        room
+    }
+
+    #[test]
+    fn test_turn_timer_warns_then_plays_for_idle_player() {
+        let mut room = started_room();
+        let timeout = 90;
+        room.game_state.as_mut().unwrap().rules.turn_timeout_secs = timeout;
+
+        let events = room.tick(1000);
+        assert!(matches!(events[0], ServerMessage::TurnTimerStarted { deadline, .. } if deadline == 1000 + timeout));
+        assert!(room.tick(1001).is_empty());
+
+        let events = room.tick(1000 + timeout - TURN_WARNING_SECS);
+        assert!(matches!(events[0], ServerMessage::TurnTimerWarning { seconds_left: TURN_WARNING_SECS, .. }));
+
+        let events = room.tick(1000 + timeout);
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::TurnTimedOut { .. })));
+        assert!(room.game_state.as_ref().unwrap().last_dice_roll.is_some());
+    }
+
+    #[test]
+    fn test_timed_out_turn_plays_out_quickly() {
+        let mut room = started_room();
+        let timeout = 90;
+        room.game_state.as_mut().unwrap().rules.turn_timeout_secs = timeout;
+        // Lands on an unowned property, which is declined and auctioned
+        room.dispatch(None, GameCommand::ScriptDice { dice: vec![(1, 2)] }, 1000).unwrap();
+        let first = room.game_state.as_ref().unwrap().acting_player_id().unwrap().to_string();
+
+        let bound = 1000 + timeout + crate::game::auction::AUCTION_DURATION_SECS + 3 * TURN_AUTO_STEP_SECS;
+        let ended = (1000..=bound).find(|now| {
+            room.tick(*now);
+            room.game_state.as_ref().unwrap().acting_player_id() != Some(first.as_str())
+        });
+        let ended = ended.expect("idle turn did not end in time");
+
+        // The next player gets the full time
+        let events = room.tick(ended + 1);
+        assert!(matches!(events[0], ServerMessage::TurnTimerStarted { deadline, .. } if deadline == ended + 1 + timeout));
+    }
+
+    #[test]
+    fn test_turn_timer_disabled() {
+        // Off unless the host turns it on
+        let mut room = started_room();
+        assert!(room.tick(1000).is_empty());
+        assert!(room.turn_clock.is_none());
+    }
+
+    #[test]
+    fn test_kick_vote_expires_with_cooldown() {
+        let mut room = started_room();
+        room.add_player(Player::new("c", "C"));
+        room.game_state.as_mut().unwrap().rules.turn_timeout_secs = 0;
+
+        room.start_vote("a", "b", VoteKind::Kick, 1000).unwrap();
+        assert!(room.start_vote("c", "b", VoteKind::Kick, 1001).is_err());
+
+        let events = room.tick(1000 + crate::room::room::VOTE_DURATION_SECS);
+        assert!(matches!(&events[0], ServerMessage::VoteFailed { reason } if reason == "Vote expired"));
+        assert!(room.vote_state.is_none());
+        assert!(room.players.contains_key("b"));
+
+        // Both the initiator and the target are on cooldown
+        let later = 1000 + crate::room::room::VOTE_DURATION_SECS + 1;
+        assert!(room.start_vote("a", "c", VoteKind::Kick, later).is_err());
+        assert!(room.start_vote("c", "b", VoteKind::Kick, later).is_err());
+        assert!(room.start_vote("c", "a", VoteKind::Kick, later).is_ok());
+    }
+
+    #[test]
+    fn test_bots_play_on_the_clock() {
+        use crate::game::bot::BotStrategy;
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        assert!(room.add_bot("b", BotStrategy::Cautious).is_err()); // Only the host adds bots
+        room.add_bot("a", BotStrategy::Cautious).unwrap();
+        room.add_bot("a", BotStrategy::Aggressive).unwrap();
+        room.remove_player("a");
+        room.seed = Some(3);
+        room.start_game();
+
+        let mut rolls = 0;
+        for now in 1000..1400 {
+            rolls += room.tick(now).iter().filter(|e| matches!(e, ServerMessage::DiceRolled { .. })).count();
+        }
+        assert!(rolls > 50, "bots rolled only {} times", rolls);
+        assert!(room.add_bot("a", BotStrategy::Cautious).is_err());
+    }
+
+    #[test]
+    fn test_bots_reject_trades_they_cannot_accept() {
+        use crate::game::bot::BotStrategy;
+        use crate::game::trade::TradeOffer;
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        let bot = room.add_bot("a", BotStrategy::Cautious).unwrap().id;
+        room.start_game();
+
+        let offer = TradeOffer { money: 1000, property_ids: Vec::new(), jail_cards: 0 };
+        let request = TradeOffer { money: 0, property_ids: Vec::new(), jail_cards: 0 };
+        let command = GameCommand::ProposeTrade { target_player_id: bot, offer, request };
+        room.dispatch(Some("a"), command, 1000).unwrap();
+        // The offer cannot be paid any more
+        let state = room.game_state.as_mut().unwrap();
+        state.players.iter_mut().find(|p| p.id == "a").unwrap().money = 0;
+
+        let events = room.play_bots(1001);
+        assert!(events.iter().any(|e| matches!(e, ServerMessage::TradeRejected { .. })));
+        assert!(room.game_state.as_ref().unwrap().active_trades.is_empty());
+    }
+
+    #[test]
+    fn test_bots_back_kick_votes_on_bots() {
+        use crate::game::bot::BotStrategy;
+        let mut room = Room::new("TEST".to_string(), Board::default(), DeckSet::default());
+        room.add_player(Player::new("a", "A"));
+        let target = room.add_bot("a", BotStrategy::Cautious).unwrap().id;
+        room.add_bot("a", BotStrategy::Aggressive).unwrap();
+        room.add_bot("a", BotStrategy::Aggressive).unwrap();
+
+        room.start_vote("a", &target, VoteKind::Kick, 1000).unwrap();
+        room.tick(1001);
+        assert!(room.vote_state.is_none());
+        assert!(!room.players.contains_key(&target));
+    }
+}
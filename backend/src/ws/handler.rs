@@ -5,10 +5,11 @@ use axum::{
 };
 use futures::{sink::SinkExt, stream::StreamExt};
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::mpsc;
 use crate::room::manager::RoomManager;
+use crate::room::room::VoteKind;
 use crate::ws::messages::{ClientMessage, ServerMessage};
-use crate::game::state::GamePhase;
+use crate::game::state::unix_now;
 
 #[tracing::instrument(skip(ws, room_manager))]
 pub async fn ws_handler(
@@ -23,7 +24,7 @@ async fn handle_socket(socket: WebSocket, room_manager: Arc<RoomManager>) {
     let (mut sender, mut receiver) = socket.split();
     let mut current_room_code: Option<String> = None;
     let mut current_player_id: Option<String> = None;
-    let mut broadcast_rx: Option<broadcast::Receiver<ServerMessage>> = None;
+    let mut outbox_rx: Option<mpsc::UnboundedReceiver<ServerMessage>> = None;
     
     tracing::info!("WebSocket connection established");
 
@@ -34,58 +35,141 @@ async fn handle_socket(socket: WebSocket, room_manager: Arc<RoomManager>) {
                     Some(Ok(msg)) => {
                         if let Message::Text(text) = msg {
                             if let Ok(client_msg) = serde_json::from_str::<ClientMessage>(&text) {
-                                match client_msg {
-                                    ClientMessage::CreateRoom { player_name } => {
-                                        let room_code = room_manager.create_room();
+                                // Spectators only watch; they may still leave or move on to another room
+                                let spectating = current_room_code.as_ref().zip(current_player_id.as_ref())
+                                    .is_some_and(|(room_code, id)| room_manager.is_spectator(room_code, id));
+                                let entering = matches!(client_msg,
+                                    ClientMessage::CreateRoom { .. } | ClientMessage::JoinRoom { .. }
+                                    | ClientMessage::JoinAsSpectator { .. } | ClientMessage::Reconnect { .. }
+                                    | ClientMessage::LeaveRoom);
+                                if spectating && !entering {
+                                    let response = ServerMessage::Error { message: "Spectators cannot take part in the game".to_string() };
+                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+                                    continue;
+                                }
+
+                                // Whether the room changed in a way worth saving
+                                let mut accepted = false;
+                                match client_msg.into_game_command() {
+                                    Ok(command) => {
+                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
+                                            match room_manager.dispatch(room_code, player_id, command) {
+                                                Ok(()) => accepted = true,
+                                                Err(e) => {
+                                                    let response = ServerMessage::Error { message: e };
+                                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+                                                }
+                                            }
+                                        }
+                                    }
+                                    Err(ClientMessage::CreateRoom { player_name, board_id }) => {
+                                        let room_code = match room_manager.create_room(board_id.as_deref()) {
+                                            Ok(room_code) => room_code,
+                                            Err(e) => {
+                                                let response = ServerMessage::Error { message: e };
+                                                let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+                                                continue;
+                                            }
+                                        };
                                         if let Some((player_id, players)) = room_manager.join_room(&room_code, player_name) {
-                                            current_room_code = Some(room_code.clone());
-                                            current_player_id = Some(player_id.clone());
-                                            
-                                            // Get broadcast channel
-                                            if let Some(room) = room_manager.rooms.get(&room_code) {
-                                                broadcast_rx = Some(room.tx.subscribe());
+                                            take_seat(&room_manager, &mut current_room_code, &mut current_player_id, &room_code, &player_id);
+                                            accepted = true;
+
+                                            // Open this socket's outbound channel
+                                            if let Some(mut room) = room_manager.rooms.get_mut(&room_code) {
+                                                outbox_rx = Some(room.subscribe(&player_id));
                                             }
 
                                             tracing::info!("Room created: {}, Player: {}", room_code, player_id);
 
                                             let response = ServerMessage::RoomCreated { 
-                                                room_code,
+                                                room_code: room_code.clone(),
                                                 player_id,
                                                 players
                                             };
                                             let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+
+                                            let lobby = [room_manager.settings_message(&room_code), room_manager.board_message(&room_code)];
+                                            for message in lobby.into_iter().flatten() {
+                                                let _ = sender.send(Message::Text(serde_json::to_string(&message).unwrap())).await;
+                                            }
                                         }
                                     }
-                                    ClientMessage::JoinRoom { room_code, player_name } => {
+                                    Err(ClientMessage::JoinRoom { room_code, player_name }) => {
                                         if let Some((player_id, players)) = room_manager.join_room(&room_code, player_name.clone()) {
-                                            current_room_code = Some(room_code.clone());
-                                            current_player_id = Some(player_id.clone());
+                                            take_seat(&room_manager, &mut current_room_code, &mut current_player_id, &room_code, &player_id);
+                                            accepted = true;
 
                                             // Broadcast PlayerJoined to others
-                                            if let Some(room) = room_manager.rooms.get(&room_code) {
-                                                let _ = room.tx.send(ServerMessage::PlayerJoined { 
-                                                    player: crate::room::player::Player { id: player_id.clone(), name: player_name } 
+                                            if let Some(mut room) = room_manager.rooms.get_mut(&room_code) {
+                                                room.send(ServerMessage::PlayerJoined { 
+                                                    player: crate::room::player::Player::new(player_id.clone(), player_name)
                                                 });
                                                 // Subscribe AFTER broadcasting to avoid receiving own join message (optional, but cleaner)
-                                                broadcast_rx = Some(room.tx.subscribe());
+                                                outbox_rx = Some(room.subscribe(&player_id));
                                             }
 
                                             tracing::info!("Joined room: {}, Player: {}", room_code, player_id);
 
                                             let response = ServerMessage::RoomJoined {
-                                                room_code,
+                                                room_code: room_code.clone(),
                                                 player_id,
                                                 players
                                             };
                                             let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+
+                                            let lobby = [
+                                                room_manager.settings_message(&room_code),
+                                                room_manager.board_message(&room_code),
+                                                room_manager.spectators_message(&room_code),
+                                                room_manager.rooms.get(&room_code).map(|room| room.chat_history(current_player_id.as_deref())),
+                                            ];
+                                            for message in lobby.into_iter().flatten() {
+                                                let _ = sender.send(Message::Text(serde_json::to_string(&message).unwrap())).await;
+                                            }
                                         }
                                      }
-                                     ClientMessage::Reconnect { room_code, player_id } => {
-                                         if let Some(room) = room_manager.rooms.get(&room_code) {
+                                     Err(ClientMessage::JoinAsSpectator { room_code, name }) => {
+                                        if let Some((spectator, players)) = room_manager.spectate_room(&room_code, name) {
+                                            take_seat(&room_manager, &mut current_room_code, &mut current_player_id, &room_code, &spectator.id);
+                                            if let Some(mut room) = room_manager.rooms.get_mut(&room_code) {
+                                                outbox_rx = Some(room.subscribe(&spectator.id));
+                                            }
+
+                                            tracing::info!("Spectating room: {}, Spectator: {}", room_code, spectator.id);
+
+                                            let response = ServerMessage::RoomSpectating {
+                                                room_code: room_code.clone(),
+                                                spectator_id: spectator.id.clone(),
+                                                players,
+                                            };
+                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+
+                                            let lobby = [
+                                                room_manager.settings_message(&room_code),
+                                                room_manager.board_message(&room_code),
+                                                room_manager.spectators_message(&room_code),
+                                                room_manager.rooms.get(&room_code).map(|room| room.chat_history(None)),
+                                            ];
+                                            for message in lobby.into_iter().flatten() {
+                                                let _ = sender.send(Message::Text(serde_json::to_string(&message).unwrap())).await;
+                                            }
+                                            let state = room_manager.rooms.get(&room_code).and_then(|room| room.game_state.clone());
+                                            if let Some(state) = state {
+                                                let state_update = ServerMessage::GameStateUpdate { state }.view_for(&spectator.id);
+                                                let _ = sender.send(Message::Text(serde_json::to_string(&state_update).unwrap())).await;
+                                            }
+                                        } else {
+                                            let response = ServerMessage::Error { message: "Room not found".to_string() };
+                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+                                        }
+                                    }
+                                     Err(ClientMessage::Reconnect { room_code, player_id }) => {
+                                         let mut seat = None;
+                                         if let Some(mut room) = room_manager.rooms.get_mut(&room_code) {
                                              if room.players.contains_key(&player_id) {
-                                                 current_room_code = Some(room_code.clone());
-                                                 current_player_id = Some(player_id.clone());
-                                                 broadcast_rx = Some(room.tx.subscribe());
+                                                 seat = Some((room_code.clone(), player_id.clone()));
+                                                 outbox_rx = Some(room.subscribe(&player_id));
 
                                                  tracing::info!("Reconnected: {}, Player: {}", room_code, player_id);
 
@@ -97,10 +181,20 @@ async fn handle_socket(socket: WebSocket, room_manager: Arc<RoomManager>) {
                                                  };
                                                  let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
 
+                                                 let settings = ServerMessage::SettingsUpdated { host_id: room.host_id.clone(), settings: room.settings.clone() };
+                                                 let _ = sender.send(Message::Text(serde_json::to_string(&settings).unwrap())).await;
+                                                 let board = ServerMessage::BoardLoaded { board: room.board.clone() };
+                                                 let _ = sender.send(Message::Text(serde_json::to_string(&board).unwrap())).await;
+                                                 let spectators = ServerMessage::SpectatorsUpdated { spectators: room.spectator_list() };
+                                                 let _ = sender.send(Message::Text(serde_json::to_string(&spectators).unwrap())).await;
+                                                 let viewer_id = seat.as_ref().map(|(_, id)| id.as_str()).unwrap_or_default();
+                                                 let history = room.chat_history(Some(viewer_id));
+                                                 let _ = sender.send(Message::Text(serde_json::to_string(&history).unwrap())).await;
+
                                                  // If game has started, send the current state
                                                  if let Some(state) = &room.game_state {
                                                      tracing::info!("Sending current game state to reconnected player. Phase: {:?}", state.phase);
-                                                     let state_update = ServerMessage::GameStateUpdate { state: state.clone() };
+                                                     let state_update = ServerMessage::GameStateUpdate { state: state.clone() }.view_for(viewer_id);
                                                      let _ = sender.send(Message::Text(serde_json::to_string(&state_update).unwrap())).await;
                                                  } else {
                                                      tracing::info!("No game state found for reconnected room");
@@ -115,502 +209,164 @@ async fn handle_socket(socket: WebSocket, room_manager: Arc<RoomManager>) {
                                              let response = ServerMessage::Error { message: "Room not found".to_string() };
                                              let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                          }
+                                         // Take the seat once the room is no longer borrowed
+                                         if let Some((room_code, player_id)) = seat {
+                                             take_seat(&room_manager, &mut current_room_code, &mut current_player_id, &room_code, &player_id);
+                                         }
                                      }
-                                     ClientMessage::StartGame => {
-                                        if let Some(room_code) = &current_room_code {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                room.start_game();
-                                                if let Some(state) = &room.game_state {
-                                                    tracing::info!("Game started in room: {}", room_code);
-                                                    let response = ServerMessage::GameStarted { state: state.clone() };
-                                                    let _ = room.tx.send(response);
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::RollDice => {
+                                     Err(ClientMessage::UpdateSettings { settings }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    let current_player_idx = state.current_turn;
-                                                    if let Some(_player) = state.players.get(current_player_idx) {
-                                                        match state.handle_roll(player_id) {
-                                                            Ok((dice, events)) => {
-                                                                let is_doubles = dice.0 == dice.1;
-                                                                let phase_before = state.phase.clone();
-                                                                
-                                                                // If sent to jail, phase is already EndTurn (set by send_to_jail called in handle_roll)
-                                                                // If not sent to jail, we are in Rolling.
-                                                                if state.phase == GamePhase::Rolling {
-                                                                    if !is_doubles {
-                                                                        state.phase = GamePhase::EndTurn;
-                                                                        tracing::info!(
-                                                                            "[FSM] RollDice handler: TRANSITION - phase: {:?} -> {:?} (not doubles)",
-                                                                            phase_before, state.phase
-                                                                        );
-                                                                    } else {
-                                                                        tracing::info!(
-                                                                            "[FSM] RollDice handler: NO TRANSITION - phase stays {:?} (doubles={})",
-                                                                            state.phase, is_doubles
-                                                                        );
-                                                                    }
-                                                                    // If doubles, stay in Rolling
-                                                                } else {
-                                                                    tracing::info!(
-                                                                        "[FSM] RollDice handler: NO HANDLER TRANSITION - phase already {:?} (set by game logic, e.g., jail)",
-                                                                        state.phase
-                                                                    );
-                                                                }
-
-                                                                let response = ServerMessage::DiceRolled { 
-                                                                    dice, 
-                                                                    state: state.clone() 
-                                                                };
-                                                                let _ = tx.send(response);
-                                                                
-                                                                let events_occurred = !events.is_empty();
-                                                                for event in events {
-                                                                    let _ = tx.send(event);
-                                                                }
-                                                                
-                                                                if events_occurred {
-                                                                    let _ = tx.send(ServerMessage::GameStateUpdate { state: state.clone() });
-                                                                }
-                                                            }
-                                                            Err(e) => {
-                                                                let response = ServerMessage::Error { message: e };
-                                                                let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                            }
-                                                        }
+                                            match room_manager.update_settings(room_code, player_id, settings) {
+                                                Ok(()) => {
+                                                    accepted = true;
+                                                    tracing::info!("Settings updated in room: {}", room_code);
+                                                    if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
+                                                        let settings = ServerMessage::SettingsUpdated {
+                                                            host_id: room.host_id.clone(),
+                                                            settings: room.settings.clone(),
+                                                        };
+                                                        room.send(settings);
                                                     }
                                                 }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::PayBail => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    let current_player_idx = state.current_turn;
-                                                    if let Some(player) = state.players.get(current_player_idx) {
-                                                        if &player.id == player_id {
-                                                            match state.pay_bail(current_player_idx) {
-                                                                Ok(events) => {
-                                                                    for event in events {
-                                                                        let _ = tx.send(event);
-                                                                    }
-                                                                    let _ = tx.send(ServerMessage::GameStateUpdate { state: state.clone() });
-                                                                }
-                                                                Err(e) => {
-                                                                    let response = ServerMessage::Error { message: e };
-                                                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                                }
-                                                            }
-                                                        }
-                                                    }
+                                                Err(e) => {
+                                                    let response = ServerMessage::Error { message: e };
+                                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::UseJailCard => {
+                                    Err(ClientMessage::AddBot { strategy }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    let current_player_idx = state.current_turn;
-                                                    if let Some(player) = state.players.get(current_player_idx) {
-                                                        if &player.id == player_id {
-                                                            match state.use_jail_card(current_player_idx) {
-                                                                Ok(events) => {
-                                                                    for event in events {
-                                                                        let _ = tx.send(event);
-                                                                    }
-                                                                    let _ = tx.send(ServerMessage::GameStateUpdate { state: state.clone() });
-                                                                }
-                                                                Err(e) => {
-                                                                    let response = ServerMessage::Error { message: e };
-                                                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                                }
-                                                            }
-                                                        }
+                                                match room.add_bot(player_id, strategy) {
+                                                    Ok(player) => {
+                                                        accepted = true;
+                                                        tracing::info!("Bot {} added to room: {}", player.id, room_code);
+                                                        room.send(ServerMessage::PlayerJoined { player });
                                                     }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::ProposeTrade { target_player_id, offer, request } => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_propose_trade(player_id.clone(), target_player_id, offer, request) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let response = ServerMessage::Error { message: e };
-                                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                        }
+                                                    Err(e) => {
+                                                        let response = ServerMessage::Error { message: e };
+                                                        let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::AcceptTrade { trade_id } => {
+                                     Err(ClientMessage::StartGame) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_accept_trade(trade_id, player_id.clone()) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let response = ServerMessage::Error { message: e };
-                                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+                                                match room.start_game_by(player_id) {
+                                                    Ok(()) => {
+                                                        if let Some(state) = room.game_state.clone() {
+                                                            accepted = true;
+                                                            tracing::info!("Game started in room: {}", room_code);
+                                                            let board = state.board.clone();
+                                                            room.send(ServerMessage::GameStarted { state, board });
                                                         }
                                                     }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::RejectTrade { trade_id } => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_reject_trade(trade_id, player_id.clone()) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let response = ServerMessage::Error { message: e };
-                                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                        }
+                                                    Err(e) => {
+                                                        let response = ServerMessage::Error { message: e };
+                                                        let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::CancelTrade { trade_id } => {
+                                    Err(ClientMessage::VoteKick { target_player_id }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_cancel_trade(trade_id, player_id.clone()) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let _ = tx.send(ServerMessage::Error { message: e });
-                                                        }
+                                                match room.start_vote(player_id, &target_player_id, VoteKind::Kick, unix_now()) {
+                                                    Ok(events) => {
+                                                        accepted = true;
+                                                        room.send_all(events);
                                                     }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::BuyBuilding { property_id } => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_buy_building(player_id.clone(), property_id) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let _ = tx.send(ServerMessage::Error { message: e });
-                                                        }
+                                                    Err(e) => {
+                                                        let response = ServerMessage::Error { message: e };
+                                                        let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::SellBuilding { property_id } => {
+                                    Err(ClientMessage::VoteMute { target_player_id }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_sell_building(player_id.clone(), property_id) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let _ = tx.send(ServerMessage::Error { message: e });
-                                                        }
+                                                match room.start_vote(player_id, &target_player_id, VoteKind::Mute, unix_now()) {
+                                                    Ok(events) => {
+                                                        accepted = true;
+                                                        room.send_all(events);
                                                     }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::MortgageProperty { property_id } => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_mortgage_property(player_id.clone(), property_id) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let _ = tx.send(ServerMessage::Error { message: e });
-                                                        }
+                                                    Err(e) => {
+                                                        let response = ServerMessage::Error { message: e };
+                                                        let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::UnmortgageProperty { property_id } => {
+                                    Err(ClientMessage::MutePlayer { target_player_id, muted }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match state.handle_unmortgage_property(player_id.clone(), property_id) {
-                                                        Ok(events) => {
-                                                            for event in events {
-                                                                let _ = tx.send(event);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let _ = tx.send(ServerMessage::Error { message: e });
-                                                        }
+                                                match room.set_muted(player_id, &target_player_id, muted) {
+                                                    Ok(event) => {
+                                                        accepted = true;
+                                                        room.send(event);
                                                     }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::DeclareBankruptcy { creditor_id } => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    tracing::info!(
-                                                        "[FSM] DeclareBankruptcy: player_id={}, creditor_id={:?}",
-                                                        player_id, creditor_id
-                                                    );
-                                                    let events = state.handle_bankruptcy(
-                                                        player_id,
-                                                        creditor_id.as_deref()
-                                                    );
-                                                    for event in events {
-                                                        let _ = tx.send(event);
+                                                    Err(e) => {
+                                                        let response = ServerMessage::Error { message: e };
+                                                        let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::BuyProperty => {
+                                    Err(ClientMessage::Chat { text, to }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            tracing::info!("Received BuyProperty request from player {} in room {}", player_id, room_code);
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                if let Some(state) = &mut room.game_state {
-                                                    match crate::game::actions::handle_buy_property(state, player_id) {
-                                                        Ok(_) => {
-                                                            let response = ServerMessage::GameStateUpdate { state: state.clone() };
-                                                            let _ = room.tx.send(response);
-                                                        }
-                                                        Err(e) => {
-                                                            let response = ServerMessage::Error { message: e };
-                                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                        }
+                                                match room.send_chat(player_id, &text, to, unix_now()) {
+                                                    Ok(event) => {
+                                                        room.send(event);
+                                                        accepted = true;
                                                     }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::PayRent => {
-                                        if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                if let Some(state) = &mut room.game_state {
-                                                    match crate::game::actions::handle_pay_rent(state, player_id) {
-                                                        Ok(result) => {
-                                                            use crate::game::actions::PayRentResult;
-                                                            match result {
-                                                                PayRentResult::Success => {
-                                                                    let response = ServerMessage::GameStateUpdate { state: state.clone() };
-                                                                    let _ = tx.send(response);
-                                                                }
-                                                                PayRentResult::BankruptcyRequired { creditor_id, rent_owed } => {
-                                                                    tracing::info!(
-                                                                        "[FSM] PayRent: AUTO-BANKRUPTCY - player_id={}, creditor_id={}, rent_owed={}",
-                                                                        player_id, creditor_id, rent_owed
-                                                                    );
-                                                                    // Auto-trigger bankruptcy - assets transfer to creditor
-                                                                    let events = state.handle_bankruptcy(
-                                                                        player_id,
-                                                                        Some(&creditor_id)
-                                                                    );
-                                                                    for event in events {
-                                                                        let _ = tx.send(event);
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            let response = ServerMessage::Error { message: e };
-                                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                        }
+                                                    Err(e) => {
+                                                        let response = ServerMessage::Error { message: e };
+                                                        let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    ClientMessage::EndTurn => {
-                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                if let Some(state) = &mut room.game_state {
-                                                    let current_player_idx = state.current_turn;
-                                                    if let Some(_player) = state.players.get(current_player_idx) {
-                                                        match state.next_turn(player_id) {
-                                                            Ok(_) => {
-                                                                let response = ServerMessage::TurnEnded { 
-                                                                    state: state.clone() 
-                                                                };
-                                                                let _ = room.tx.send(response);
-                                                            }
-                                                            Err(e) => {
-                                                                let response = ServerMessage::Error { message: e };
-                                                                let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    ClientMessage::VoteKick { target_player_id } => {
+                                    Err(ClientMessage::CastVote { vote }) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
                                             if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                // Check if vote already in progress
-                                                if room.vote_state.is_some() {
-                                                    let response = ServerMessage::Error { message: "Vote already in progress".to_string() };
-                                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                    continue;
-                                                }
-
-                                                // Check if target exists
-                                                if !room.players.contains_key(&target_player_id) {
-                                                    let response = ServerMessage::Error { message: "Player not found".to_string() };
-                                                    let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
-                                                    continue;
-                                                }
-
-                                                // Initialize vote
-                                                let end_time = std::time::SystemTime::now()
-                                                    .duration_since(std::time::UNIX_EPOCH)
-                                                    .unwrap()
-                                                    .as_secs() + 30;
-
-                                                let mut votes_for = std::collections::HashSet::new();
-                                                votes_for.insert(player_id.clone()); // Initiator automatically votes yes
-
-                                                let vote_state = crate::room::room::VoteState {
-                                                    initiator_id: player_id.clone(),
-                                                    target_player_id: target_player_id.clone(),
-                                                    votes_for,
-                                                    votes_against: std::collections::HashSet::new(),
-                                                    end_time,
-                                                };
-
-                                                room.vote_state = Some(vote_state);
-
-                                                let response = ServerMessage::VoteStarted {
-                                                    initiator_id: player_id.clone(),
-                                                    target_player_id: target_player_id.clone(),
-                                                    end_time,
-                                                };
-                                                let _ = room.tx.send(response);
-
-                                                // Send initial update
-                                                let required = (room.players.len() as f32 / 2.0).floor() as usize + 1;
-                                                let update = ServerMessage::VoteUpdate {
-                                                    votes_for: 1,
-                                                    votes_against: 0,
-                                                    required,
-                                                };
-                                                let _ = room.tx.send(update);
+                                                let events = room.cast_vote(player_id, vote, unix_now());
+                                                accepted = !events.is_empty();
+                                                room.send_all(events);
                                             }
                                         }
                                     }
-                                    ClientMessage::CastVote { vote } => {
+                                    Err(message @ (ClientMessage::LeaveRoom | ClientMessage::Forfeit)) => {
                                         if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
-                                            if let Some(mut room) = room_manager.rooms.get_mut(room_code) {
-                                                let tx = room.tx.clone();
-                                                let total_players = room.players.len();
-                                                if let Some(vote_state) = &mut room.vote_state {
-                                                    if vote {
-                                                        vote_state.votes_for.insert(player_id.clone());
-                                                        vote_state.votes_against.remove(player_id);
-                                                    } else {
-                                                        vote_state.votes_against.insert(player_id.clone());
-                                                        vote_state.votes_for.remove(player_id);
-                                                    }
-
-                                                    let votes_for_count = vote_state.votes_for.len();
-                                                    let votes_against_count = vote_state.votes_against.len();
-                                                    // Remaining players = total players - 1 (the target)
-                                                    // Actually, usually the target cannot vote, or their vote doesn't count towards the majority of *others*.
-                                                    // Let's say target CAN vote (to save themselves).
-                                                    // Majority of ALL players? Or majority of remaining players if target is removed?
-                                                    // Plan said: ">50% of remaining players".
-                                                    // Let's assume target is excluded from "remaining players".
-                                                    
-                                                    let remaining_players = if total_players > 0 { total_players - 1 } else { 0 };
-                                                    let required = (remaining_players as f32 / 2.0).floor() as usize + 1;
-
-                                                    // Check if target voted (if we want to exclude them, we should filter them out or not let them vote)
-                                                    // For simplicity, let's just count everyone but set threshold based on N-1.
-                                                    // If target votes NO, it helps them.
-                                                    
-                                                    let update = ServerMessage::VoteUpdate {
-                                                        votes_for: votes_for_count,
-                                                        votes_against: votes_against_count,
-                                                        required,
-                                                    };
-                                                    let _ = tx.send(update);
-
-                                                    if votes_for_count >= required {
-                                                        // Kick successful
-                                                        let target_id = vote_state.target_player_id.clone();
-                                                        
-                                                        // Remove from game state
-                                                        if let Some(game_state) = &mut room.game_state {
-                                                            game_state.remove_player(&target_id);
-                                                        }
-                                                        
-                                                        // Remove from room
-                                                        room.remove_player(&target_id);
-                                                        room.vote_state = None;
-
-                                                        let response = ServerMessage::PlayerKicked { player_id: target_id };
-                                                        let _ = tx.send(response);
-                                                    } else if votes_against_count > (remaining_players - required) {
-                                                        // Vote failed (impossible to reach majority)
-                                                        room.vote_state = None;
-                                                        let response = ServerMessage::VoteFailed { reason: "Not enough votes".to_string() };
-                                                        let _ = tx.send(response);
-                                                    }
+                                            let forfeit = matches!(message, ClientMessage::Forfeit);
+                                            let response = match room_manager.leave_room(room_code, player_id, forfeit) {
+                                                Ok(()) => {
+                                                    let response = ServerMessage::RoomLeft { room_code: room_code.clone() };
+                                                    outbox_rx = None;
+                                                    current_room_code = None;
+                                                    current_player_id = None;
+                                                    response
                                                 }
-                                            }
+                                                Err(e) => ServerMessage::Error { message: e },
+                                            };
+                                            let _ = sender.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
                                         }
                                     }
+                                    Err(_) => {} // Remaining messages are game commands, dispatched above
+                                }
+
+                                // Snapshot the room so it survives a server restart
+                                if let Some(room_code) = current_room_code.as_ref().filter(|_| accepted) {
+                                    room_manager.persist(room_code);
                                 }
                             } else {
                                 tracing::error!("Failed to parse client message: {}", text);
@@ -625,20 +381,21 @@ async fn handle_socket(socket: WebSocket, room_manager: Arc<RoomManager>) {
                 }
             }
             recv_result = async { 
-                if let Some(rx) = &mut broadcast_rx {
+                if let Some(rx) = &mut outbox_rx {
                     rx.recv().await
                 } else {
                     std::future::pending().await
                 }
             } => {
                 match recv_result {
-                    Ok(msg) => {
+                    Some(msg) => {
                         if let Ok(json) = serde_json::to_string(&msg) {
                             let _ = sender.send(Message::Text(json)).await;
                         }
                     }
-                    Err(_) => {
-                        // Lagged or closed
+                    None => {
+                        // The room is gone
+                        outbox_rx = None;
                     }
                 }
             }
@@ -646,4 +403,26 @@ async fn handle_socket(socket: WebSocket, room_manager: Arc<RoomManager>) {
     }
 
     tracing::info!("WebSocket disconnected. Room: {:?}, Player: {:?}", current_room_code, current_player_id);
+    if let (Some(room_code), Some(player_id)) = (&current_room_code, &current_player_id) {
+        room_manager.disconnect(room_code, player_id);
+    }
+}
+
+/// Points this socket at a seat, releasing the one it held before
+fn take_seat(
+    room_manager: &RoomManager,
+    current_room_code: &mut Option<String>,
+    current_player_id: &mut Option<String>,
+    room_code: &str,
+    player_id: &str,
+) {
+    if current_room_code.as_deref() == Some(room_code) && current_player_id.as_deref() == Some(player_id) {
+        return;
+    }
+    if let (Some(old_room), Some(old_player)) = (current_room_code.as_deref(), current_player_id.as_deref()) {
+        room_manager.disconnect(old_room, old_player);
+    }
+    room_manager.connect(room_code, player_id);
+    *current_room_code = Some(room_code.to_string());
+    *current_player_id = Some(player_id.to_string());
 }
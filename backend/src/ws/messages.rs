@@ -1,20 +1,38 @@
-use serde::{Deserialize, Serialize};
-use crate::room::player::Player;
-use crate::game::state::GameState;
+use serde::{Deserialize, Serialize, Serializer};
+use crate::room::player::{Player, Spectator};
+use crate::game::state::{GameState, GamePhase};
+use crate::game::board::Board;
 use crate::game::state::Card;
-use crate::game::trade::{TradeProposal, TradeOffer};
+use crate::game::trade::{MortgageDecision, TradeProposal, TradeOffer};
+use crate::game::auction::Auction;
+use crate::game::debt::Debt;
+use crate::game::command::GameCommand;
+use crate::game::bot::BotStrategy;
+use crate::game::rules::EstatePolicy;
+use crate::room::settings::RoomSettings;
+use crate::room::chat::ChatMessage;
+use crate::room::room::VoteKind;
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum ClientMessage {
-    CreateRoom { player_name: String },
+    CreateRoom { player_name: String, #[serde(default)] board_id: Option<String> },
     JoinRoom { room_code: String, player_name: String },
+    JoinAsSpectator { room_code: String, name: String },
+    UpdateSettings { settings: RoomSettings },
+    AddBot { strategy: BotStrategy },
     StartGame,
     RollDice,
     BuyProperty,
+    DeclineProperty,
+    PlaceBid { amount: i32 },
+    PassAuction,
     PayRent,
     EndTurn,
     VoteKick { target_player_id: String },
+    VoteMute { target_player_id: String },
+    MutePlayer { target_player_id: String, muted: bool },
+    Chat { text: String, #[serde(default)] to: Option<String> },
     CastVote { vote: bool },
     PayBail,
     UseJailCard,
@@ -22,12 +40,47 @@ pub enum ClientMessage {
     AcceptTrade { trade_id: String },
     RejectTrade { trade_id: String },
     CancelTrade { trade_id: String },
+    CounterTrade { trade_id: String, offer: TradeOffer, request: TradeOffer },
     BuyBuilding { property_id: usize },
     SellBuilding { property_id: usize },
     MortgageProperty { property_id: usize },
     UnmortgageProperty { property_id: usize },
-    DeclareBankruptcy { creditor_id: Option<String> },
+    ResolveMortgage { property_id: usize, unmortgage: bool },
+    PayDebt,
+    DeclareBankruptcy,
     Reconnect { room_code: String, player_id: String },
+    LeaveRoom,
+    Forfeit,
+}
+
+impl ClientMessage {
+    /// Splits off the messages that act on the running game
+    pub fn into_game_command(self) -> Result<GameCommand, ClientMessage> {
+        Ok(match self {
+            ClientMessage::RollDice => GameCommand::RollDice,
+            ClientMessage::BuyProperty => GameCommand::BuyProperty,
+            ClientMessage::DeclineProperty => GameCommand::DeclineProperty,
+            ClientMessage::PlaceBid { amount } => GameCommand::PlaceBid { amount },
+            ClientMessage::PassAuction => GameCommand::PassAuction,
+            ClientMessage::PayRent => GameCommand::PayRent,
+            ClientMessage::PayDebt => GameCommand::PayDebt,
+            ClientMessage::DeclareBankruptcy => GameCommand::DeclareBankruptcy,
+            ClientMessage::EndTurn => GameCommand::EndTurn,
+            ClientMessage::PayBail => GameCommand::PayBail,
+            ClientMessage::UseJailCard => GameCommand::UseJailCard,
+            ClientMessage::ProposeTrade { target_player_id, offer, request } => GameCommand::ProposeTrade { target_player_id, offer, request },
+            ClientMessage::AcceptTrade { trade_id } => GameCommand::AcceptTrade { trade_id },
+            ClientMessage::RejectTrade { trade_id } => GameCommand::RejectTrade { trade_id },
+            ClientMessage::CancelTrade { trade_id } => GameCommand::CancelTrade { trade_id },
+            ClientMessage::CounterTrade { trade_id, offer, request } => GameCommand::CounterTrade { trade_id, offer, request },
+            ClientMessage::BuyBuilding { property_id } => GameCommand::BuyBuilding { property_id },
+            ClientMessage::SellBuilding { property_id } => GameCommand::SellBuilding { property_id },
+            ClientMessage::MortgageProperty { property_id } => GameCommand::MortgageProperty { property_id },
+            ClientMessage::UnmortgageProperty { property_id } => GameCommand::UnmortgageProperty { property_id },
+            ClientMessage::ResolveMortgage { property_id, unmortgage } => GameCommand::ResolveMortgage { property_id, unmortgage },
+            other => return Err(other),
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -36,25 +89,103 @@ pub enum ServerMessage {
     RoomCreated { room_code: String, player_id: String, players: Vec<Player> },
     PlayerJoined { player: Player },
     RoomJoined { room_code: String, player_id: String, players: Vec<Player> },
-    GameStarted { state: GameState },
-    DiceRolled { dice: (u8, u8), state: GameState },
-    GameStateUpdate { state: GameState },
-    TurnEnded { state: GameState },
-    VoteStarted { initiator_id: String, target_player_id: String, end_time: u64 },
+    RoomSpectating { room_code: String, spectator_id: String, players: Vec<Player> },
+    SpectatorsUpdated { spectators: Vec<Spectator> },
+    SettingsUpdated { host_id: Option<String>, settings: RoomSettings },
+    BoardLoaded { board: Board },
+    GameStarted { #[serde(serialize_with = "public_state")] state: GameState, board: Board },
+    DiceRolled { dice: (u8, u8), #[serde(serialize_with = "public_state")] state: GameState },
+    GameStateUpdate { #[serde(serialize_with = "public_state")] state: GameState },
+    TurnEnded { #[serde(serialize_with = "public_state")] state: GameState },
+    TurnTimerStarted { player_id: String, phase: GamePhase, deadline: u64 },
+    TurnTimerWarning { player_id: String, seconds_left: u64 },
+    TurnTimedOut { player_id: String },
+    VoteStarted { kind: VoteKind, initiator_id: String, target_player_id: String, end_time: u64 },
     VoteUpdate { votes_for: usize, votes_against: usize, required: usize },
     PlayerKicked { player_id: String },
+    PlayerLeft { player_id: String, forfeited: bool },
+    RoomLeft { room_code: String },
     VoteFailed { reason: String },
+    Chat { message: ChatMessage },
+    ChatHistory { messages: Vec<ChatMessage> },
+    PlayerMuted { player_id: String, muted: bool },
     CardDrawn { card: Card, is_chance: bool },
     JailStateUpdated { player_id: String, is_in_jail: bool, jail_turns: u8 },
+    FreeParkingPayout { player_id: String, amount: i32 },
     TradeProposed { proposal: TradeProposal },
-    TradeAccepted { trade_id: String },
+    TradeNegotiating { trade_id: String, initiator_id: String, target_player_id: String },
+    TradeAccepted { trade_id: String, proposal: TradeProposal },
     TradeRejected { trade_id: String },
     TradeCancelled { trade_id: String },
     BuildingBought { property_id: usize, houses: u8 },
     BuildingSold { property_id: usize, houses: u8 },
     PropertyMortgaged { property_id: usize, mortgage_value: i32 },
     PropertyUnmortgaged { property_id: usize, cost: i32 },
+    MortgageDecisionRequired { decision: MortgageDecision },
+    AuctionStarted { auction: Auction },
+    AuctionBidPlaced { player_id: String, amount: i32, ends_at: u64 },
+    AuctionPassed { player_id: String },
+    AuctionEnded { property_id: usize, winner_id: Option<String>, amount: i32 },
+    DebtIncurred { debt: Debt },
+    DebtPaid { debtor_id: String, creditor_id: Option<String>, amount: i32 },
+    EstateSettled { player_id: String, policy: EstatePolicy, property_ids: Vec<usize>, bot_id: Option<String> },
     PlayerBankrupt { player_id: String, player_name: String, creditor_id: Option<String> },
     GameOver { winner_id: String, winner_name: String },
+    PlayerAway { player_id: String, takeover_at: u64 },
+    PlayerTakenOver { player_id: String },
+    PlayerReturned { player_id: String },
     Error { message: String },
 }
+
+impl ServerMessage {
+    /// The message as one player or spectator may see it: trades between
+    /// other players are left out of the game state
+    pub fn view_for(&self, viewer_id: &str) -> ServerMessage {
+        let mut message = self.clone();
+        if let ServerMessage::GameStarted { state, .. }
+            | ServerMessage::DiceRolled { state, .. }
+            | ServerMessage::GameStateUpdate { state }
+            | ServerMessage::TurnEnded { state } = &mut message
+        {
+            state.active_trades.retain(|_, trade| trade.involves(viewer_id));
+        }
+        message
+    }
+}
+
+/// Game state as players see it. The RNG and the order of the card decks are
+/// left out so nobody can work out the upcoming rolls or cards; only the
+/// number of cards left in each deck is sent.
+pub fn public_state_value(state: &GameState) -> serde_json::Result<serde_json::Value> {
+    let mut value = serde_json::to_value(state)?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("rng");
+        fields.remove("chance_deck");
+        fields.remove("community_chest_deck");
+        fields.insert("chance_deck_size".to_string(), state.chance_deck.len().into());
+        fields.insert("community_chest_deck_size".to_string(), state.community_chest_deck.len().into());
+    }
+    Ok(value)
+}
+
+fn public_state<S: Serializer>(state: &GameState, serializer: S) -> Result<S::Ok, S::Error> {
+    public_state_value(state).map_err(serde::ser::Error::custom)?.serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_state_hides_rng_and_decks() {
+        let state = GameState::new();
+        assert!(!state.chance_deck.is_empty());
+        let message = serde_json::to_value(ServerMessage::GameStateUpdate { state: state.clone() }).unwrap();
+        let public = &message["state"];
+        for hidden in ["rng", "chance_deck", "community_chest_deck"] {
+            assert!(public.get(hidden).is_none(), "{} is sent to players", hidden);
+        }
+        assert_eq!(public["chance_deck_size"], state.chance_deck.len());
+        assert_eq!(public["community_chest_deck_size"], state.community_chest_deck.len());
+    }
+}
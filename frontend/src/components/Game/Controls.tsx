@@ -20,9 +20,6 @@ export const Controls = () => {
     const now = Date.now();
     const canRollDebounce = (now - lastRollTime) > ROLL_DEBOUNCE_MS;
 
-    // Combined roll check: must be my turn, in Rolling phase, not already rolling, and debounce passed
-    const canRoll = isMyTurn && gameState.phase === 'Rolling' && !isRolling && canRollDebounce;
-
     // Property Logic
     const currentPosition = currentPlayer.position;
     const tileInfo = getTile(currentPosition);
@@ -32,7 +29,15 @@ export const Controls = () => {
     const isOwned = propertyState?.owner_id != null;
     const isMyProperty = propertyState?.owner_id === playerId;
 
-    const canBuy = isMyTurn && gameState.phase === 'EndTurn' && propertyInfo && !isOwned && propertyInfo.price !== undefined && currentPlayer.money >= propertyInfo.price;
+    // A tile reached with doubles is bought or declined before rolling again
+    const offerPending = isMyTurn && propertyInfo && propertyState && !isOwned && !gameState.offer_declined;
+    const canDecide = gameState.phase === 'EndTurn' || (gameState.phase === 'Rolling' && offerPending);
+
+    // Combined roll check: must be my turn, in Rolling phase, not already rolling, and debounce passed
+    const canRoll = isMyTurn && gameState.phase === 'Rolling' && !offerPending && !isRolling && canRollDebounce;
+
+    const canBuy = isMyTurn && canDecide && propertyInfo && !isOwned && propertyInfo.price !== undefined && currentPlayer.money >= propertyInfo.price;
+    const canDecline = isMyTurn && canDecide && offerPending;
     const canPayRent = isMyTurn && gameState.phase === 'EndTurn' && propertyInfo && isOwned && !isMyProperty && propertyInfo.rent !== undefined && !gameState.rent_paid;
 
     const canEndTurn = isMyTurn && gameState.phase === 'EndTurn';
@@ -131,6 +136,15 @@ export const Controls = () => {
                             </button>
                         )}
 
+                        {canDecline && (
+                            <button
+                                onClick={() => sendMessage({ type: 'DeclineProperty' })}
+                                className="px-6 py-3 rounded-lg font-bold transition-colors bg-orange-500 hover:bg-orange-600 text-white"
+                            >
+                                Auction
+                            </button>
+                        )}
+
                         {canPayRent && propertyInfo && propertyInfo.rent !== undefined && (
                             <button
                                 onClick={() => sendMessage({ type: 'PayRent' })}
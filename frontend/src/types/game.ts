@@ -40,8 +40,9 @@ export interface GameState {
     current_turn: number;
     phase: GamePhase;
     rent_paid: boolean;
-    chance_deck: Card[];
-    community_chest_deck: Card[];
+    offer_declined: boolean;
+    chance_deck_size: number;
+    community_chest_deck_size: number;
     active_trades: Record<string, TradeProposal>;
     total_houses: number;
     total_hotels: number;
@@ -70,6 +71,7 @@ export type ClientMessage =
     | { type: 'StartGame' }
     | { type: 'RollDice' }
     | { type: 'BuyProperty' }
+    | { type: 'DeclineProperty' }
     | { type: 'PayRent' }
     | { type: 'EndTurn' }
     | { type: 'VoteKick'; target_player_id: string }